@@ -1,9 +1,13 @@
 use crate::core::color::Color;
+use crate::core::png;
+use std::fs;
 use std::fs::File;
+use std::io;
 use std::io::prelude::*;
 use std::path::Path;
 
 //Canvas stores the color for each pixel
+#[derive(Debug, PartialEq, Clone)]
 pub struct Canvas {
     pub width: usize,
     pub height: usize,
@@ -86,20 +90,150 @@ impl Canvas {
         result
     }
 
-    //Write the canvas to a ppm file
-    pub fn write_file(canvas: Canvas, filename: &str) {
-        let filename_formatted = &*format!("{}.ppm", filename);
-        let path = Path::new(filename_formatted);
-        let display = path.display();
-        let text = Canvas::format_ppm(canvas);
-        let mut file = match File::create(&path) {
-            Err(error) => panic!("Failed to open {}: {}", display, error),
-            Ok(file) => file,
+    //Converts the canvas contents to rows of RGB bytes
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.contents.len() * 3);
+        for color in &self.contents {
+            bytes.extend_from_slice(&color.to_bytes());
+        }
+        bytes
+    }
+
+    //Formats the canvas contents as a binary (P6) ppm
+    pub fn format_ppm_binary(canvas: &Canvas) -> Vec<u8> {
+        let mut result = format!("P6\n{} {}\n255\n", &canvas.width, &canvas.height).into_bytes();
+        result.extend_from_slice(&canvas.to_bytes());
+        result
+    }
+
+    //Formats the canvas contents as a png
+    pub fn format_png(canvas: &Canvas) -> Vec<u8> {
+        png::encode(canvas.width, canvas.height, &canvas.to_bytes())
+    }
+
+    //Writes the canvas to a file, choosing the format from its extension (.ppm or .png)
+    pub fn write_file(canvas: &Canvas, filename: &str) -> io::Result<()> {
+        let path = Path::new(filename);
+        let extension = path.extension().and_then(|extension| extension.to_str()).map(|extension| extension.to_lowercase());
+        let bytes = match extension.as_deref() {
+            Some("ppm") => Canvas::format_ppm_binary(canvas),
+            Some("png") => Canvas::format_png(canvas),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} does not end with a supported image extension (.ppm or .png)", path.display()),
+                ))
+            }
         };
-        match file.write_all(text.as_bytes()) {
-            Err(error) => panic!("Failed to write to {}: {}", display, error),
-            Ok(_) => println!("Wrote canvas to {}", display),
+        fs::write(path, bytes)
+    }
+
+    //Creates a canvas from rows of RGB bytes
+    fn from_bytes(width: usize, height: usize, bytes: &[u8]) -> Canvas {
+        let mut canvas = Canvas::new(width, height);
+        for (color, pixel) in canvas.contents.iter_mut().zip(bytes.chunks(3)) {
+            *color = Color::new_255(pixel[0] as i32, pixel[1] as i32, pixel[2] as i32);
+        }
+        canvas
+    }
+
+    //Reads a canvas from a file, choosing the format from its contents
+    pub fn read_file(filename: &str) -> io::Result<Canvas> {
+        let bytes = fs::read(filename)?;
+        if bytes.starts_with(b"P3") || bytes.starts_with(b"P6") {
+            Canvas::parse_ppm(&bytes)
+        }
+        else {
+            Canvas::parse_png(&bytes)
+        }
+    }
+
+    //Reads a png image
+    pub fn parse_png(bytes: &[u8]) -> io::Result<Canvas> {
+        let (width, height, rgb) = png::decode(bytes)?;
+        Ok(Canvas::from_bytes(width, height, &rgb))
+    }
+
+    //Reads an ascii (P3) or binary (P6) ppm image
+    pub fn parse_ppm(bytes: &[u8]) -> io::Result<Canvas> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+        let mut position = 0;
+
+        //Reads the next whitespace separated item, skipping comments
+        let next_item = |position: &mut usize| -> io::Result<String> {
+            loop {
+                while *position < bytes.len() && bytes[*position].is_ascii_whitespace() {
+                    *position += 1;
+                }
+                if *position < bytes.len() && bytes[*position] == b'#' {
+                    while *position < bytes.len() && bytes[*position] != b'\n' {
+                        *position += 1;
+                    }
+                }
+                else {
+                    break;
+                }
+            }
+            let start = *position;
+            while *position < bytes.len() && !bytes[*position].is_ascii_whitespace() {
+                *position += 1;
+            }
+            if start == *position {
+                return Err(invalid("ppm ended early"));
+            }
+            Ok(String::from_utf8_lossy(&bytes[start..*position]).to_string())
+        };
+        let next_number = |position: &mut usize| -> io::Result<usize> {
+            next_item(position)?.parse().map_err(|_| invalid("expected a number in the ppm"))
+        };
+
+        let magic = next_item(&mut position)?;
+        if magic != "P3" && magic != "P6" {
+            return Err(invalid("ppm must start with P3 or P6"));
+        }
+        let width = next_number(&mut position)?;
+        let height = next_number(&mut position)?;
+        let max = next_number(&mut position)?;
+        if width == 0 || height == 0 {
+            return Err(invalid("ppm width and height must not be zero"));
+        }
+        if max == 0 || max > 65535 {
+            return Err(invalid("ppm maximum value must be between 1 and 65535"));
+        }
+
+        //Checks the header against the bytes left in the file before anything is allocated
+        let size = if max > 255 { 2 } else { 1 };
+        let count = width.checked_mul(height).and_then(|pixels| pixels.checked_mul(3)).ok_or_else(|| invalid("ppm size is too large"))?;
+        let mut values = vec![];
+        if magic == "P3" {
+            //Every value takes at least one digit and the separator before it
+            if count > (bytes.len() - position) / 2 {
+                return Err(invalid("ppm pixel data is too short"));
+            }
+            values.reserve(count);
+            for _ in 0..count {
+                values.push(next_number(&mut position)?);
+            }
+        }
+        else {
+            //A single whitespace character separates the header from the pixel bytes
+            position += 1;
+            let remaining = bytes.len().saturating_sub(position);
+            if count.checked_mul(size).is_none_or(|needed| needed > remaining) {
+                return Err(invalid("ppm pixel data is too short"));
+            }
+            values.reserve(count);
+            for i in 0..count {
+                let index = position + i * size;
+                values.push(if size == 2 { (bytes[index] as usize) << 8 | bytes[index + 1] as usize } else { bytes[index] as usize });
+            }
+        }
+
+        let mut canvas = Canvas::new(width, height);
+        for (color, pixel) in canvas.contents.iter_mut().zip(values.chunks(3)) {
+            *color = Color::new(pixel[0] as f32 / max as f32, pixel[1] as f32 / max as f32, pixel[2] as f32 / max as f32);
         }
+        Ok(canvas)
     }
 
     //Write the canvas to a txt file
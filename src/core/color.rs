@@ -35,6 +35,12 @@ impl Color {
         format!("{} {} {}", fixed_color.0.round() as i32, fixed_color.1.round() as i32, fixed_color.2.round() as i32)
     }
 
+    //Converts the color to bytes for binary image formats
+    pub fn to_bytes(&self) -> [u8; 3] {
+        let fixed_color = self.clamp().convert();
+        [fixed_color.0.round() as u8, fixed_color.1.round() as u8, fixed_color.2.round() as u8]
+    }
+
     //Takes a color and fully converts it to charaxcter
     pub fn txt_string(&self) -> String {
         let fixed_color = self.clamp().convert();
@@ -80,6 +86,11 @@ impl Color {
         (self.ppm_string().len() as i32) + 1
     }
 
+    //Keeps each channel between a minimum and maximum value
+    pub fn clamp_range(&self, min: f32, max: f32) -> Color {
+        Color::new(clamp_float(self.0, min, max), clamp_float(self.1, min, max), clamp_float(self.2, min, max))
+    }
+
     //Rounds colors for testing
     pub fn round(&self) -> Color {
         Color::new(((self.0 * 10000.0).round())/10000.0, ((self.1 * 10000.0).round())/10000.0, ((self.2 * 10000.0).round())/10000.0)
@@ -21,11 +21,12 @@ pub struct Comp {
     pub material: Material,
     pub n1: f32, //Refraction index of the object the ray is passing form
     pub n2: f32, //Refraction index of the object the ray is passing to
-    pub parent_inverses: Vec<Matrix4x4>
+    pub parent_inverses: Vec<Matrix4x4>,
+    pub time: f32, //Time in the shutter interval of the ray which hit the point
 }
 
 impl Comp {
-    //Creates a new Comp
+    //Creates a new Comp for a ray sent when the shutter opens
     pub fn new(
         t: f32,
         material: Material,
@@ -55,6 +56,7 @@ impl Comp {
             n1,
             n2,
             parent_inverses,
+            time: 0.0,
         }
     }
 
@@ -85,13 +87,7 @@ impl Comp {
         let mut n1 = 1.0;
         let mut n2 = 1.0;
 
-        let object_material;
-        if intersection.object.get_parent_material() != &None {
-            object_material = intersection.object.get_parent_material().as_ref().unwrap().clone()
-        }
-        else {
-            object_material = intersection.object.get_material().clone();
-        }
+        let object_material = intersection.object.get_effective_material().clone();
 
         let mut containers: Vec<&dyn Object> = vec![];
         for i in intersection_list {
@@ -117,10 +113,10 @@ impl Comp {
                 }
             }
         }
-        Comp::new(
+        Comp {
             t,
-            object_material,
-            intersection.object.get_inverse().clone(),
+            material: object_material,
+            object_inverse: intersection.object.get_inverse_at(ray.time),
             point,
             e_vec,
             n_vec,
@@ -130,7 +126,8 @@ impl Comp {
             under_point,
             n1,
             n2,
-            intersection.object.get_parent_inverses().clone(),
-        )
+            parent_inverses: intersection.object.get_parent_inverses().clone(),
+            time: ray.time,
+        }
     }
 }
@@ -99,10 +99,10 @@ impl Matrix4x4 {
         determinant
     }
 
-    //Finds the inverse of a Matrix4x4
+    //Finds the inverse of a Matrix4x4, which only exists when its determinant is not zero
     pub fn inverse(&self) -> Option<Matrix4x4> {
         let det = Matrix4x4::determinant(&self);
-        if det < 0.0 {
+        if det == 0.0 {
             None
         } else {
             let cofactor_matrix = Matrix4x4::new(
@@ -226,6 +226,19 @@ impl Matrix4x4 {
         )
     }
 
+    //Blends two matrices entry by entry, giving this matrix at 0.0 and the other at 1.0
+    pub fn lerp(&self, other: &Matrix4x4, amount: f32) -> Matrix4x4 {
+        let blend = |a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)| {
+            (
+                a.0 + (b.0 - a.0) * amount,
+                a.1 + (b.1 - a.1) * amount,
+                a.2 + (b.2 - a.2) * amount,
+                a.3 + (b.3 - a.3) * amount,
+            )
+        };
+        Matrix4x4::new(blend(self.0, other.0), blend(self.1, other.1), blend(self.2, other.2), blend(self.3, other.3))
+    }
+
     //Rounds a matrix for testing
     pub fn round(&self) -> Matrix4x4 {
         let mut m = vec![
@@ -758,10 +771,10 @@ impl Matrix3x3 {
         determinant
     }
 
-    //Finds the inverse of a Matrix4x4
+    //Finds the inverse of a Matrix4x4, which only exists when its determinant is not zero
     pub fn inverse(self) -> Option<Matrix3x3> {
         let det = Matrix3x3::determinant(&self);
-        if det < 0.0 {
+        if det == 0.0 {
             None
         } else {
             let cofactor_matrix = Matrix3x3::new(
@@ -1,9 +1,10 @@
 pub mod canvas;
 pub mod color;
+pub mod png;
 
 pub mod matrix;
 pub mod vector;
 
 pub mod comp;
 
-pub mod sequence;
+pub mod sequence;
\ No newline at end of file
@@ -0,0 +1,617 @@
+//A small self-contained PNG encoder and decoder
+//Images are written as 8-bit RGB compressed with the fixed Huffman codes of deflate
+//Any non-interlaced PNG can be read back, with alpha channels being dropped
+
+use std::io;
+
+const SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+//Lengths and distances are stored as a base value plus a number of extra bits
+pub const LENGTH_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+pub const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+pub const DISTANCE_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097,
+    6145, 8193, 12289, 16385, 24577,
+];
+pub const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+//Distance back which matches are searched for and how many candidates are checked
+const WINDOW_SIZE: usize = 32768;
+const MAX_CHAIN: usize = 64;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+
+//Encodes rows of RGB bytes as a PNG file
+pub fn encode(width: usize, height: usize, rgb: &[u8]) -> Vec<u8> {
+    let mut header = vec![];
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    //8 bits per channel, RGB color, deflate compression, adaptive filtering, no interlacing
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut result = SIGNATURE.to_vec();
+    write_chunk(&mut result, b"IHDR", &header);
+    write_chunk(&mut result, b"IDAT", &zlib_compress(&filter_rows(width, height, rgb)));
+    write_chunk(&mut result, b"IEND", &[]);
+    result
+}
+
+//Writes the length, type, data and checksum of a chunk
+fn write_chunk(output: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = output.len();
+    output.extend_from_slice(kind);
+    output.extend_from_slice(data);
+    let crc = crc32(&output[start..]);
+    output.extend_from_slice(&crc.to_be_bytes());
+}
+
+//Filters each row with whichever filter gives the smallest sum of differences
+//Smaller differences compress better since they repeat more often
+fn filter_rows(width: usize, height: usize, rgb: &[u8]) -> Vec<u8> {
+    let stride = width * 3;
+    let empty = vec![0; stride];
+    //Each row is the row's bytes and a filter type, so the size comes from the rgb bytes rather than the dimensions
+    let mut result = Vec::with_capacity(rgb.len() + height);
+    for y in 0..height {
+        let row = &rgb[(y * stride)..((y + 1) * stride)];
+        let above = if y == 0 { &empty[..] } else { &rgb[((y - 1) * stride)..(y * stride)] };
+        let mut best = (0, vec![], u64::MAX);
+        for filter in 0..5 {
+            let filtered: Vec<u8> = (0..stride)
+                .map(|i| {
+                    let left = if i >= 3 { row[i - 3] } else { 0 };
+                    let upper_left = if i >= 3 { above[i - 3] } else { 0 };
+                    row[i].wrapping_sub(predict(filter, left, above[i], upper_left))
+                })
+                .collect();
+            let score = filtered.iter().map(|&byte| (byte as i8).unsigned_abs() as u64).sum();
+            if score < best.2 {
+                best = (filter, filtered, score);
+            }
+        }
+        result.push(best.0);
+        result.extend_from_slice(&best.1);
+    }
+    result
+}
+
+//Predicts a byte from its neighbours using one of the five PNG filters
+pub fn predict(filter: u8, left: u8, above: u8, upper_left: u8) -> u8 {
+    match filter {
+        1 => left,
+        2 => above,
+        3 => ((left as u16 + above as u16) / 2) as u8,
+        4 => {
+            let estimate = left as i16 + above as i16 - upper_left as i16;
+            let left_distance = (estimate - left as i16).abs();
+            let above_distance = (estimate - above as i16).abs();
+            let upper_left_distance = (estimate - upper_left as i16).abs();
+            if left_distance <= above_distance && left_distance <= upper_left_distance {
+                left
+            }
+            else if above_distance <= upper_left_distance {
+                above
+            }
+            else {
+                upper_left
+            }
+        }
+        _ => 0,
+    }
+}
+
+//Wraps deflated data with a zlib header and checksum
+pub fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut result = vec![0x78, 0x01];
+    result.extend_from_slice(&deflate(data));
+    result.extend_from_slice(&adler32(data).to_be_bytes());
+    result
+}
+
+//Writes values into a stream of bits starting from the lowest bit of each byte
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        self.buffer |= value << self.count;
+        self.count += bits;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    //Huffman codes are stored starting from their highest bit
+    fn write_code(&mut self, code: u32, bits: u32) {
+        let mut reversed = 0;
+        for i in 0..bits {
+            reversed |= ((code >> i) & 1) << (bits - 1 - i);
+        }
+        self.write(reversed, bits);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+//Finds the fixed Huffman code and its length for a literal or length symbol
+fn fixed_code(symbol: u32) -> (u32, u32) {
+    match symbol {
+        0..=143 => (0x30 + symbol, 8),
+        144..=255 => (0x190 + symbol - 144, 9),
+        256..=279 => (symbol - 256, 7),
+        _ => (0xc0 + symbol - 280, 8),
+    }
+}
+
+//Finds the index of the last base which is not above a value
+fn base_index(bases: &[u16], value: usize) -> usize {
+    bases.iter().rposition(|&base| base as usize <= value).unwrap()
+}
+
+//Compresses data into a single deflate block using the fixed Huffman codes
+//Repeated runs of bytes are found with a hash chain over every 3 byte sequence
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter {
+        bytes: vec![],
+        buffer: 0,
+        count: 0,
+    };
+    //Final block using the fixed codes
+    writer.write(1, 1);
+    writer.write(1, 2);
+
+    let mut head = vec![usize::MAX; 1 << 15];
+    let mut previous = vec![usize::MAX; data.len()];
+
+    let mut i = 0;
+    while i < data.len() {
+        let mut best_length = 0;
+        let mut best_distance = 0;
+        if i + MIN_MATCH <= data.len() {
+            let mut candidate = head[hash(data, i)];
+            let mut chain = 0;
+            while candidate != usize::MAX && i - candidate <= WINDOW_SIZE && chain < MAX_CHAIN {
+                let limit = MAX_MATCH.min(data.len() - i);
+                let mut length = 0;
+                while length < limit && data[candidate + length] == data[i + length] {
+                    length += 1;
+                }
+                if length > best_length {
+                    best_length = length;
+                    best_distance = i - candidate;
+                    if length == limit {
+                        break;
+                    }
+                }
+                candidate = previous[candidate];
+                chain += 1;
+            }
+        }
+
+        if best_length >= MIN_MATCH {
+            let length_index = base_index(&LENGTH_BASES, best_length);
+            let (code, bits) = fixed_code(257 + length_index as u32);
+            writer.write_code(code, bits);
+            writer.write((best_length - LENGTH_BASES[length_index] as usize) as u32, LENGTH_EXTRA_BITS[length_index] as u32);
+            let distance_index = base_index(&DISTANCE_BASES, best_distance);
+            writer.write_code(distance_index as u32, 5);
+            writer.write((best_distance - DISTANCE_BASES[distance_index] as usize) as u32, DISTANCE_EXTRA_BITS[distance_index] as u32);
+            for j in i..(i + best_length) {
+                insert(data, j, &mut head, &mut previous);
+            }
+            i += best_length;
+        }
+        else {
+            let (code, bits) = fixed_code(data[i] as u32);
+            writer.write_code(code, bits);
+            insert(data, i, &mut head, &mut previous);
+            i += 1;
+        }
+    }
+    let (code, bits) = fixed_code(256);
+    writer.write_code(code, bits);
+    writer.finish()
+}
+
+//Hashes the 3 bytes starting at an index
+fn hash(data: &[u8], i: usize) -> usize {
+    (((data[i] as usize) << 10) ^ ((data[i + 1] as usize) << 5) ^ data[i + 2] as usize) & 0x7fff
+}
+
+//Adds the sequence starting at an index to the front of its hash chain
+fn insert(data: &[u8], i: usize, head: &mut [usize], previous: &mut [usize]) {
+    if i + MIN_MATCH <= data.len() {
+        let key = hash(data, i);
+        previous[i] = head[key];
+        head[key] = i;
+    }
+}
+
+//Checksum used by PNG chunks
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffff;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            if crc & 1 == 1 {
+                crc = (crc >> 1) ^ 0xedb88320;
+            }
+            else {
+                crc >>= 1;
+            }
+        }
+    }
+    !crc
+}
+
+//Checksum used by zlib streams
+pub fn adler32(data: &[u8]) -> u32 {
+    let mut a = 1;
+    let mut b = 0;
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+//Creates the error returned for malformed files
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+//Reads a PNG file into its width, height and rows of RGB bytes
+pub fn decode(bytes: &[u8]) -> io::Result<(usize, usize, Vec<u8>)> {
+    if bytes.len() < 8 || bytes[..8] != SIGNATURE {
+        return Err(invalid("missing png signature"));
+    }
+    let mut header = None;
+    let mut palette = vec![];
+    let mut compressed = vec![];
+    let mut position = 8;
+    loop {
+        if position + 12 > bytes.len() {
+            return Err(invalid("png ended before the IEND chunk"));
+        }
+        let length = u32::from_be_bytes([bytes[position], bytes[position + 1], bytes[position + 2], bytes[position + 3]]) as usize;
+        if position + 12 + length > bytes.len() {
+            return Err(invalid("png chunk is longer than the file"));
+        }
+        let kind = &bytes[(position + 4)..(position + 8)];
+        let data = &bytes[(position + 8)..(position + 8 + length)];
+        let stored_crc = &bytes[(position + 8 + length)..(position + 12 + length)];
+        if crc32(&bytes[(position + 4)..(position + 8 + length)]).to_be_bytes() != stored_crc {
+            return Err(invalid("png chunk checksum does not match"));
+        }
+        match kind {
+            b"IHDR" => {
+                if length != 13 {
+                    return Err(invalid("png header has the wrong length"));
+                }
+                let width = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+                let height = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+                if width == 0 || height == 0 {
+                    return Err(invalid("png width and height must not be zero"));
+                }
+                if data[12] != 0 {
+                    return Err(invalid("interlaced pngs are not supported"));
+                }
+                header = Some((width, height, data[8], data[9]));
+            }
+            b"PLTE" => palette = data.to_vec(),
+            b"IDAT" => compressed.extend_from_slice(data),
+            b"IEND" => break,
+            _ => (),
+        }
+        position += 12 + length;
+    }
+
+    let (width, height, depth, color_type) = match header {
+        Some(header) => header,
+        None => return Err(invalid("png has no header")),
+    };
+    let channels = match (color_type, depth) {
+        (0, 1) | (0, 2) | (0, 4) | (0, 8) | (0, 16) => 1,
+        (3, 1) | (3, 2) | (3, 4) | (3, 8) => 1,
+        (2, 8) | (2, 16) => 3,
+        (4, 8) | (4, 16) => 2,
+        (6, 8) | (6, 16) => 4,
+        _ => return Err(invalid("unsupported png color type or bit depth")),
+    };
+    let bits_per_pixel = channels * depth as usize;
+
+    //Checks the sizes from the header can be stored before comparing them against the data or allocating
+    let too_large = || invalid("png size is too large");
+    let stride = width.checked_mul(bits_per_pixel).ok_or_else(too_large)?.div_ceil(8);
+    let needed = (stride + 1).checked_mul(height).ok_or_else(too_large)?;
+    let count = width.checked_mul(height).and_then(|pixels| pixels.checked_mul(3)).ok_or_else(too_large)?;
+    let filtered = zlib_decompress(&compressed)?;
+    if filtered.len() < needed {
+        return Err(invalid("png image data is too short"));
+    }
+    let raw = unfilter_rows(&filtered, stride, height, bits_per_pixel.div_ceil(8))?;
+
+    //Converts each pixel to 8-bit RGB
+    let mut rgb = Vec::with_capacity(count);
+    for y in 0..height {
+        let row = &raw[(y * stride)..((y + 1) * stride)];
+        for x in 0..width {
+            let sample = |channel: usize| -> usize {
+                let index = x * channels + channel;
+                match depth {
+                    16 => row[index * 2] as usize,
+                    8 => row[index] as usize,
+                    _ => {
+                        let bit = index * depth as usize;
+                        let shift = 8 - depth as usize - bit % 8;
+                        ((row[bit / 8] >> shift) & ((1 << depth) - 1)) as usize
+                    }
+                }
+            };
+            match color_type {
+                0 => {
+                    let gray = if depth < 8 { sample(0) * 255 / ((1 << depth) - 1) } else { sample(0) };
+                    rgb.extend_from_slice(&[gray as u8; 3]);
+                }
+                3 => {
+                    let index = sample(0) * 3;
+                    if index + 3 > palette.len() {
+                        return Err(invalid("png palette index is out of range"));
+                    }
+                    rgb.extend_from_slice(&palette[index..(index + 3)]);
+                }
+                4 => rgb.extend_from_slice(&[sample(0) as u8; 3]),
+                _ => rgb.extend_from_slice(&[sample(0) as u8, sample(1) as u8, sample(2) as u8]),
+            }
+        }
+    }
+    Ok((width, height, rgb))
+}
+
+//Undoes the filter at the start of each row
+fn unfilter_rows(filtered: &[u8], stride: usize, height: usize, pixel_bytes: usize) -> io::Result<Vec<u8>> {
+    let mut result = vec![0; stride * height];
+    for y in 0..height {
+        let filter = filtered[y * (stride + 1)];
+        if filter > 4 {
+            return Err(invalid("unknown png filter"));
+        }
+        for i in 0..stride {
+            let left = if i >= pixel_bytes { result[y * stride + i - pixel_bytes] } else { 0 };
+            let above = if y > 0 { result[(y - 1) * stride + i] } else { 0 };
+            let upper_left = if y > 0 && i >= pixel_bytes { result[(y - 1) * stride + i - pixel_bytes] } else { 0 };
+            let byte = filtered[y * (stride + 1) + 1 + i];
+            result[y * stride + i] = byte.wrapping_add(predict(filter, left, above, upper_left));
+        }
+    }
+    Ok(result)
+}
+
+//Checks the zlib header and checksum around deflated data
+pub fn zlib_decompress(data: &[u8]) -> io::Result<Vec<u8>> {
+    if data.len() < 6 || data[0] & 0x0f != 8 || !((data[0] as u16) << 8 | data[1] as u16).is_multiple_of(31) {
+        return Err(invalid("invalid zlib header"));
+    }
+    if data[1] & 0x20 != 0 {
+        return Err(invalid("zlib preset dictionaries are not supported"));
+    }
+    let result = inflate(&data[2..])?;
+    let checksum = &data[(data.len() - 4)..];
+    if adler32(&result).to_be_bytes() != checksum {
+        return Err(invalid("zlib checksum does not match"));
+    }
+    Ok(result)
+}
+
+//Reads values from a stream of bits starting from the lowest bit of each byte
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    bit: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn read(&mut self, bits: u32) -> io::Result<u32> {
+        let mut value = 0;
+        for i in 0..bits {
+            if self.position >= self.bytes.len() {
+                return Err(invalid("deflate data ended early"));
+            }
+            value |= (((self.bytes[self.position] >> self.bit) & 1) as u32) << i;
+            self.bit += 1;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.position += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    //Skips to the start of the next byte
+    fn align(&mut self) {
+        if self.bit > 0 {
+            self.bit = 0;
+            self.position += 1;
+        }
+    }
+}
+
+//A canonical Huffman code stored as the number of codes of each length and the symbols in code order
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    //Builds the code from the length of each symbol's code
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0; 16];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0; 16];
+        for i in 1..16 {
+            offsets[i] = offsets[i - 1] + counts[i - 1];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length > 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+        Huffman {
+            counts,
+            symbols,
+        }
+    }
+
+    //Reads one symbol a bit at a time
+    fn decode(&self, reader: &mut BitReader) -> io::Result<u16> {
+        let mut code = 0;
+        let mut first = 0;
+        let mut index = 0;
+        for length in 1..16 {
+            code |= reader.read(1)? as i32;
+            let count = self.counts[length] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid("invalid huffman code"))
+    }
+}
+
+//Order in which the lengths of the code length code are stored
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+//Decompresses deflate data made of stored, fixed or dynamic Huffman blocks
+pub fn inflate(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut reader = BitReader {
+        bytes: data,
+        position: 0,
+        bit: 0,
+    };
+    let mut output = vec![];
+    loop {
+        let last = reader.read(1)? == 1;
+        match reader.read(2)? {
+            0 => {
+                reader.align();
+                let position = reader.position;
+                if position + 4 > data.len() {
+                    return Err(invalid("deflate data ended early"));
+                }
+                let length = u16::from_le_bytes([data[position], data[position + 1]]) as usize;
+                let complement = u16::from_le_bytes([data[position + 2], data[position + 3]]) as usize;
+                if length != !complement & 0xffff || position + 4 + length > data.len() {
+                    return Err(invalid("invalid stored deflate block"));
+                }
+                output.extend_from_slice(&data[(position + 4)..(position + 4 + length)]);
+                reader.position = position + 4 + length;
+            }
+            1 => {
+                let mut lengths = [0; 288];
+                for (symbol, length) in lengths.iter_mut().enumerate() {
+                    *length = fixed_code(symbol as u32).1 as u8;
+                }
+                inflate_block(&mut reader, &mut output, &Huffman::new(&lengths), &Huffman::new(&[5; 30]))?;
+            }
+            2 => {
+                let (literals, distances) = read_dynamic_codes(&mut reader)?;
+                inflate_block(&mut reader, &mut output, &literals, &distances)?;
+            }
+            _ => return Err(invalid("invalid deflate block type")),
+        }
+        if last {
+            return Ok(output);
+        }
+    }
+}
+
+//Reads the Huffman codes stored at the start of a dynamic block
+fn read_dynamic_codes(reader: &mut BitReader) -> io::Result<(Huffman, Huffman)> {
+    let literal_count = reader.read(5)? as usize + 257;
+    let distance_count = reader.read(5)? as usize + 1;
+    let code_length_count = reader.read(4)? as usize + 4;
+    let mut code_lengths = [0; 19];
+    for &index in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_lengths[index] = reader.read(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths);
+
+    let mut lengths = vec![];
+    while lengths.len() < literal_count + distance_count {
+        let symbol = code_length_code.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => match lengths.last() {
+                Some(&previous) => (previous, 3 + reader.read(2)?),
+                None => return Err(invalid("deflate length repeat has nothing to repeat")),
+            },
+            17 => (0, 3 + reader.read(3)?),
+            _ => (0, 11 + reader.read(7)?),
+        };
+        for _ in 0..repeat {
+            lengths.push(value);
+        }
+    }
+    if lengths.len() > literal_count + distance_count {
+        return Err(invalid("deflate code lengths overflow"));
+    }
+    Ok((Huffman::new(&lengths[..literal_count]), Huffman::new(&lengths[literal_count..])))
+}
+
+//Decodes literals and length/distance pairs until the end of a block
+fn inflate_block(reader: &mut BitReader, output: &mut Vec<u8>, literals: &Huffman, distances: &Huffman) -> io::Result<()> {
+    loop {
+        let symbol = literals.decode(reader)? as usize;
+        if symbol < 256 {
+            output.push(symbol as u8);
+        }
+        else if symbol == 256 {
+            return Ok(());
+        }
+        else {
+            let index = symbol - 257;
+            if index >= LENGTH_BASES.len() {
+                return Err(invalid("invalid deflate length"));
+            }
+            let length = LENGTH_BASES[index] as usize + reader.read(LENGTH_EXTRA_BITS[index] as u32)? as usize;
+            let index = distances.decode(reader)? as usize;
+            if index >= DISTANCE_BASES.len() {
+                return Err(invalid("invalid deflate distance"));
+            }
+            let distance = DISTANCE_BASES[index] as usize + reader.read(DISTANCE_EXTRA_BITS[index] as u32)? as usize;
+            if distance > output.len() {
+                return Err(invalid("deflate distance is further back than the output"));
+            }
+            //Copies one byte at a time since the copy may overlap itself
+            let start = output.len() - distance;
+            for i in 0..length {
+                output.push(output[start + i]);
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-//The Sequence struct is used to generate offset values for AreaLights
+//The Sequence struct is used to generate offset values for area lights
 #[derive(Debug, PartialEq)]
 pub struct Sequence {
     pub contents: Vec<f32>,
@@ -22,6 +22,24 @@ impl Sequence {
         }
     }
 
+    //Creates a low discrepancy Halton sequence of values between 0 and 1 in a given base
+    //Values are spread out evenly no matter how many of them are used
+    pub fn halton(base: usize, count: usize) -> Sequence {
+        let mut contents = Vec::with_capacity(count);
+        for index in 1..=count {
+            let mut value = 0.0;
+            let mut fraction = 1.0;
+            let mut remaining = index;
+            while remaining > 0 {
+                fraction /= base as f32;
+                value += fraction * (remaining % base) as f32;
+                remaining /= base;
+            }
+            contents.push(value);
+        }
+        Sequence::new(contents)
+    }
+
     //Moves to the next reference
     pub fn next(&mut self) -> f32 {
         let index = self.current_index.clone();
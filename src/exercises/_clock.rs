@@ -16,7 +16,7 @@ pub fn _draw() {
         //Rotates the point by 30 degrees
         point = Matrix4x4::rotation(Axis::Z, 30.0) * point;
     }
-    Canvas::write_file(canvas, "result");
+    Canvas::write_file(&canvas, "result.ppm").unwrap();
 }
 
 //Converts the point coordinates to a pixel on the canvas
@@ -21,7 +21,7 @@ pub fn _launch_projectile() {
         }
     }
     println!("Object landed at x:{}, y:{}, z:{}", position.0, position.1, position.2);
-    Canvas::write_file(canvas, "image");
+    Canvas::write_file(&canvas, "image.ppm").unwrap();
 }
 
 //Converts the projectile position to image coordinates
@@ -17,6 +17,8 @@ pub struct Material {
     pub environment_lighting: f32,
     pub casts_shadows: bool,
     pub pattern: Option<Box<dyn Pattern>>,
+    pub emission: Color, //Light given off by the material
+    pub light_surface: bool, //Marks the visible surface of a light, whose light is already gathered by sampling the light
 }
 
 impl Material {
@@ -46,6 +48,8 @@ impl Material {
             environment_lighting,
             casts_shadows,
             pattern,
+            emission: Color::new(0.0, 0.0, 0.0),
+            light_surface: false,
         }
     }
 
@@ -83,6 +87,8 @@ impl Material {
             environment_lighting: 0.0,
             casts_shadows: true,
             pattern: None,
+            emission: Color::new(0.0, 0.0, 0.0),
+            light_surface: false,
         }
     }
 }
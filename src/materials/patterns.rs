@@ -1,12 +1,15 @@
+use crate::core::canvas::Canvas;
 use crate::core::color::Color;
 use crate::core::vector::Vec4;
 use crate::core::matrix::Matrix4x4;
 use crate::objects::object::*;
+use std::f32::consts::PI;
 use std::fmt::Debug;
 use std::any::Any;
+use std::sync::Arc;
 
 //Generic enum pattern which matches to specific patterns
-pub trait Pattern: Debug + PatternClone {
+pub trait Pattern: Debug + PatternClone + Send + Sync {
     //Gets the color at a point on the pattern
     fn color_at(&self, point: &Vec4) -> Color;
     
@@ -291,4 +294,209 @@ impl Pattern for CheckerboardPattern  {
     }
 
     fn as_any(&self) -> &dyn Any { self }
-}
\ No newline at end of file
+}
+
+//Ways a point on an object is turned into (u, v) coordinates on a texture
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UvMapping {
+    //Wraps the texture around a unit sphere with u following longitude and v following latitude
+    Spherical,
+    //Repeats the texture every unit across the xz plane
+    Planar,
+    //Wraps the texture around the y axis and repeats it every unit along y
+    Cylindrical,
+    //Puts one part of a cross shaped texture on each face of a unit cube
+    Cube,
+}
+
+//Ways colors are read from the pixels of a texture
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TextureFilter {
+    //Uses the closest pixel
+    Nearest,
+    //Blends the four closest pixels
+    Bilinear,
+}
+
+//Faces of a cube (the column and row of each face in a cross shaped texture are given in cube_face_cell)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CubeFace {
+    Left,
+    Right,
+    Front,
+    Back,
+    Up,
+    Down,
+}
+
+//Finds the (u, v) coordinates of a point on a unit sphere
+pub fn spherical_map(point: &Vec4) -> (f32, f32) {
+    let theta = point.0.atan2(point.2);
+    let radius = (point.0 * point.0 + point.1 * point.1 + point.2 * point.2).sqrt();
+    let phi = (point.1 / radius).clamp(-1.0, 1.0).acos();
+    let raw_u = theta / (2.0 * PI);
+    (1.0 - (raw_u + 0.5), 1.0 - phi / PI)
+}
+
+//Finds the (u, v) coordinates of a point on a plane
+pub fn planar_map(point: &Vec4) -> (f32, f32) {
+    (point.0.rem_euclid(1.0), point.2.rem_euclid(1.0))
+}
+
+//Finds the (u, v) coordinates of a point on a cylinder
+pub fn cylindrical_map(point: &Vec4) -> (f32, f32) {
+    let theta = point.0.atan2(point.2);
+    let raw_u = theta / (2.0 * PI);
+    (1.0 - (raw_u + 0.5), point.1.rem_euclid(1.0))
+}
+
+//Finds which face of a unit cube a point is on
+pub fn cube_face(point: &Vec4) -> CubeFace {
+    let max = point.0.abs().max(point.1.abs()).max(point.2.abs());
+    if max == point.0 {
+        CubeFace::Right
+    }
+    else if max == -point.0 {
+        CubeFace::Left
+    }
+    else if max == point.1 {
+        CubeFace::Up
+    }
+    else if max == -point.1 {
+        CubeFace::Down
+    }
+    else if max == point.2 {
+        CubeFace::Front
+    }
+    else {
+        CubeFace::Back
+    }
+}
+
+//Finds the (u, v) coordinates of a point within its cube face
+pub fn cube_map(point: &Vec4) -> (CubeFace, f32, f32) {
+    let face = cube_face(point);
+    //Points on the edges of a face are clamped to it, as wrapping would move them to the opposite edge
+    let scale = |value: f32| (value / 2.0).clamp(0.0, 1.0);
+    let (u, v) = match face {
+        CubeFace::Front => (scale(point.0 + 1.0), scale(point.1 + 1.0)),
+        CubeFace::Back => (scale(1.0 - point.0), scale(point.1 + 1.0)),
+        CubeFace::Left => (scale(point.2 + 1.0), scale(point.1 + 1.0)),
+        CubeFace::Right => (scale(1.0 - point.2), scale(point.1 + 1.0)),
+        CubeFace::Up => (scale(point.0 + 1.0), scale(1.0 - point.2)),
+        CubeFace::Down => (scale(point.0 + 1.0), scale(point.2 + 1.0)),
+    };
+    (face, u, v)
+}
+
+//Finds the column and row of a face in a cross shaped texture 4 faces wide and 3 faces tall
+//  [  ][Up][  ][  ]
+//  [Lf][Fr][Rt][Bk]
+//  [  ][Dn][  ][  ]
+pub fn cube_face_cell(face: CubeFace) -> (usize, usize) {
+    match face {
+        CubeFace::Up => (1, 0),
+        CubeFace::Left => (0, 1),
+        CubeFace::Front => (1, 1),
+        CubeFace::Right => (2, 1),
+        CubeFace::Back => (3, 1),
+        CubeFace::Down => (1, 2),
+    }
+}
+
+//Reads the color at (u, v) within a rectangle of a canvas, where v = 0 is the bottom of the rectangle
+pub fn sample_canvas(canvas: &Canvas, filter: TextureFilter, u: f32, v: f32, region: (usize, usize, usize, usize)) -> Color {
+    let (left, top, width, height) = region;
+    let u = u.clamp(0.0, 1.0);
+    let v = v.clamp(0.0, 1.0);
+    let pixel = |x: i32, y: i32| {
+        let x = x.clamp(0, width as i32 - 1) + left as i32;
+        let y = y.clamp(0, height as i32 - 1) + top as i32;
+        canvas.get(x, y).cloned().unwrap_or(Color::new(0.0, 0.0, 0.0))
+    };
+    match filter {
+        TextureFilter::Nearest => pixel((u * width as f32) as i32, ((1.0 - v) * height as f32) as i32),
+        TextureFilter::Bilinear => {
+            //Pixel centers lie half a pixel in from the edges
+            let x = u * width as f32 - 0.5;
+            let y = (1.0 - v) * height as f32 - 0.5;
+            let (x0, y0) = (x.floor(), y.floor());
+            let (fx, fy) = (x - x0, y - y0);
+            let (x0, y0) = (x0 as i32, y0 as i32);
+            let top_color = pixel(x0, y0) * (1.0 - fx) + pixel(x0 + 1, y0) * fx;
+            let bottom_color = pixel(x0, y0 + 1) * (1.0 - fx) + pixel(x0 + 1, y0 + 1) * fx;
+            top_color * (1.0 - fy) + bottom_color * fy
+        }
+    }
+}
+
+//A pattern which reads its colors from an image
+#[derive(Debug, PartialEq, Clone)]
+pub struct TexturePattern {
+    canvas: Arc<Canvas>,
+    mapping: UvMapping,
+    filter: TextureFilter,
+    transform: Matrix4x4,
+    inverse: Matrix4x4,
+}
+
+impl TexturePattern {
+    //Creates a new TexturePattern (the canvas is shared so many objects can use one image)
+    pub fn new(canvas: Arc<Canvas>, mapping: UvMapping, filter: TextureFilter, transform: Matrix4x4) -> TexturePattern {
+        TexturePattern {
+            inverse: transform.inverse().unwrap(),
+            canvas,
+            mapping,
+            filter,
+            transform,
+        }
+    }
+}
+
+impl Pattern for TexturePattern {
+    //Gets the color at a specific point
+    fn color_at(&self, point: &Vec4) -> Color {
+        let whole = (0, 0, self.canvas.width, self.canvas.height);
+        let (u, v, region) = match self.mapping {
+            UvMapping::Spherical => {
+                let (u, v) = spherical_map(point);
+                (u, v, whole)
+            }
+            UvMapping::Planar => {
+                let (u, v) = planar_map(point);
+                (u, v, whole)
+            }
+            UvMapping::Cylindrical => {
+                let (u, v) = cylindrical_map(point);
+                (u, v, whole)
+            }
+            UvMapping::Cube => {
+                let (face, u, v) = cube_map(point);
+                let (column, row) = cube_face_cell(face);
+                let width = self.canvas.width / 4;
+                let height = self.canvas.height / 3;
+                (u, v, (column * width, row * height, width.max(1), height.max(1)))
+            }
+        };
+        sample_canvas(&self.canvas, self.filter, u, v, region)
+    }
+
+    //Transforms the pattern
+    fn transform(&mut self, matrix: Matrix4x4) {
+        self.transform = &self.transform * matrix;
+    }
+
+    //Gets the color at a specific point taking into account pattern and object transformations
+    fn color_at_object(&self, list: &Vec<Matrix4x4>, object_inverse: &Matrix4x4, point: &Vec4) -> Color {
+        let group_point = world_to_object(list, point);
+        let object_point = object_inverse * group_point;
+        let pattern_point = &self.inverse * object_point;
+        self.color_at(&pattern_point)
+    }
+
+    fn eq(&self, other: &dyn Pattern) -> bool {
+        other.as_any().downcast_ref::<Self>().is_some_and(|x| x == self)
+    }
+
+    fn as_any(&self) -> &dyn Any { self }
+}
@@ -1,2 +1,5 @@
 pub mod axis;
-pub mod utils;
\ No newline at end of file
+pub mod options;
+pub mod random;
+pub mod utils;
+pub mod yaml;
\ No newline at end of file
@@ -0,0 +1,315 @@
+use crate::world::antialiasing::*;
+use crate::world::camera::Camera;
+use crate::world::settings::*;
+use crate::world::stereo::*;
+use std::str::FromStr;
+
+pub const USAGE: &str = "Usage: rust_ray_tracer <scene file> [options]
+
+Options:
+  -o, --output <path>      File the image is written to, as .ppm or .png (default: image.ppm)
+  --width <pixels>         Width of the image (keeps the aspect ratio if no height is given)
+  --height <pixels>        Height of the image (keeps the aspect ratio if no width is given)
+  --fov <degrees>          Field of view of the camera
+  -m, --mode <mode>        render, supersampled, adaptive or quick (default: render)
+  -i, --integrator <name>  whitted or path (default: whitted)
+  -d, --depth <bounces>    Maximum number of reflection and refraction bounces, up to 100 (default: 5)
+  -s, --samples <rays>     Rays per pixel when supersampling or path tracing (default: 4)
+  --sampler <name>         grid, jittered or random placement of rays in a pixel (default: grid)
+  --filter <name>          box, tent, gaussian or mitchell weighting of rays in a pixel (default: box)
+  --threshold <amount>     Color difference which makes adaptive renders send more rays (default: 0.1)
+  --adaptive-depth <n>     Times adaptive renders may split a pixel into quarters (default: 2)
+  --heatmap <path>         File the rays per pixel of an adaptive render are written to
+  --frames <first>-<last>  Renders each frame of the scene's animation to a numbered file, replacing #s in the
+                           output path with the frame number or adding it before the extension
+  --stereo <layout>        Renders left and right eyes side-by-side or top-bottom on one image
+  --interocular <distance> Distance between the eyes of stereo renders (default: 1/30 of the convergence distance)
+  --convergence <distance> Distance where the eyes of stereo renders converge (default: the camera's focal distance)
+  --toe-in                 Turns the eyes of stereo renders inwards instead of shifting their views off-axis
+  -t, --threads <count>    Number of render threads (default: all available cores)
+  --shadow-floor <amount>  Lowest light intensity in shadows, from 0 to 1 (default: 0)
+  --opaque-shadows         Makes transparent objects cast shadows as dark as opaque ones
+  --seed <number>          Seed for random sampling, the same seed always gives the same image (default: 0)
+  -q, --quiet              Hides render progress and status messages
+  --help                   Prints this message";
+
+//Largest number of bounces which can be given with --depth
+pub const MAX_DEPTH: i32 = 100;
+
+//The ways a scene can be rendered
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RenderMode {
+    Render,
+    Supersampled,
+    Adaptive,
+    Quick,
+}
+
+//Options read from the command line
+//Settings which are not given are left as they are in the scene file
+#[derive(Debug, PartialEq, Clone)]
+pub struct Options {
+    pub scene: String,
+    pub output: String,
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub fov: Option<f32>,
+    pub mode: RenderMode,
+    pub integrator: Option<Integrator>,
+    pub depth: Option<i32>,
+    pub samples: Option<usize>,
+    pub sampler: Option<Sampler>,
+    pub filter: Option<Filter>,
+    pub threshold: Option<f32>,
+    pub adaptive_depth: Option<usize>,
+    pub heatmap: Option<String>,
+    pub frames: Option<(usize, usize)>, //First and last frame of an animation to render
+    pub stereo: Option<Layout>,
+    pub interocular: Option<f32>,
+    pub convergence: Option<f32>,
+    pub toe_in: bool,
+    pub threads: Option<usize>,
+    pub shadow_floor: Option<f32>,
+    pub opaque_shadows: bool,
+    pub seed: Option<u64>,
+    pub quiet: bool,
+    pub help: bool,
+}
+
+impl Options {
+    //Reads options from a list of arguments (not including the program name)
+    pub fn parse(args: &[String]) -> Result<Options, String> {
+        let mut scene = None;
+        let mut options = Options {
+            scene: String::new(),
+            output: String::from("image.ppm"),
+            width: None,
+            height: None,
+            fov: None,
+            mode: RenderMode::Render,
+            integrator: None,
+            depth: None,
+            samples: None,
+            sampler: None,
+            filter: None,
+            threshold: None,
+            adaptive_depth: None,
+            heatmap: None,
+            frames: None,
+            stereo: None,
+            interocular: None,
+            convergence: None,
+            toe_in: false,
+            threads: None,
+            shadow_floor: None,
+            opaque_shadows: false,
+            seed: None,
+            quiet: false,
+            help: false,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-o" | "--output" => options.output = Options::value(arg, args.next())?.to_string(),
+                "--width" => options.width = Some(Options::number(arg, args.next())?),
+                "--height" => options.height = Some(Options::number(arg, args.next())?),
+                "--fov" => options.fov = Some(Options::number(arg, args.next())?),
+                "-d" | "--depth" => options.depth = Some(Options::number(arg, args.next())?),
+                "-s" | "--samples" => options.samples = Some(Options::number(arg, args.next())?),
+                "--threshold" => options.threshold = Some(Options::number(arg, args.next())?),
+                "--adaptive-depth" => options.adaptive_depth = Some(Options::number(arg, args.next())?),
+                "--heatmap" => options.heatmap = Some(Options::value(arg, args.next())?.to_string()),
+                "--frames" => options.frames = Some(Options::frames(Options::value(arg, args.next())?)?),
+                "--interocular" => options.interocular = Some(Options::number(arg, args.next())?),
+                "--convergence" => options.convergence = Some(Options::number(arg, args.next())?),
+                "--toe-in" => options.toe_in = true,
+                "-t" | "--threads" => options.threads = Some(Options::number(arg, args.next())?),
+                "--shadow-floor" => options.shadow_floor = Some(Options::number(arg, args.next())?),
+                "--opaque-shadows" => options.opaque_shadows = true,
+                "--seed" => options.seed = Some(Options::number(arg, args.next())?),
+                "-m" | "--mode" => {
+                    options.mode = match Options::value(arg, args.next())? {
+                        "render" => RenderMode::Render,
+                        "supersampled" => RenderMode::Supersampled,
+                        "adaptive" => RenderMode::Adaptive,
+                        "quick" => RenderMode::Quick,
+                        other => return Err(format!("unknown render mode '{}'", other)),
+                    }
+                }
+                "-i" | "--integrator" => {
+                    options.integrator = match Options::value(arg, args.next())? {
+                        "whitted" => Some(Integrator::Whitted),
+                        "path" => Some(Integrator::PathTracer),
+                        other => return Err(format!("unknown integrator '{}'", other)),
+                    }
+                }
+                "--sampler" => {
+                    options.sampler = match Options::value(arg, args.next())? {
+                        "grid" => Some(Sampler::Grid),
+                        "jittered" => Some(Sampler::Jittered),
+                        "random" => Some(Sampler::Random),
+                        other => return Err(format!("unknown sampler '{}'", other)),
+                    }
+                }
+                "--filter" => {
+                    options.filter = match Options::value(arg, args.next())? {
+                        "box" => Some(Filter::Box),
+                        "tent" => Some(Filter::Tent),
+                        "gaussian" => Some(Filter::Gaussian),
+                        "mitchell" => Some(Filter::Mitchell),
+                        other => return Err(format!("unknown filter '{}'", other)),
+                    }
+                }
+                "--stereo" => {
+                    options.stereo = match Options::value(arg, args.next())? {
+                        "side-by-side" => Some(Layout::SideBySide),
+                        "top-bottom" => Some(Layout::TopBottom),
+                        other => return Err(format!("unknown stereo layout '{}'", other)),
+                    }
+                }
+                "-q" | "--quiet" => options.quiet = true,
+                "--help" => options.help = true,
+                _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+                _ if scene.is_none() => scene = Some(arg.to_string()),
+                _ => return Err(format!("unexpected argument '{}'", arg)),
+            }
+        }
+        match scene {
+            Some(scene) => options.scene = scene,
+            None if options.help => (),
+            None => return Err(String::from("no scene file given")),
+        }
+        if options.width == Some(0) || options.height == Some(0) {
+            return Err(String::from("the width and height must be above 0"));
+        }
+        if options.depth.is_some_and(|depth| !(0..=MAX_DEPTH).contains(&depth)) {
+            return Err(format!("the depth must be between 0 and {}", MAX_DEPTH));
+        }
+        if options.samples == Some(0) {
+            return Err(String::from("at least one sample is needed per pixel"));
+        }
+        if options.threshold.is_some_and(|threshold| threshold < 0.0) {
+            return Err(String::from("the adaptive threshold must not be negative"));
+        }
+        if options.heatmap.is_some() && options.mode != RenderMode::Adaptive {
+            return Err(String::from("a heatmap can only be written by adaptive renders"));
+        }
+        if options.stereo.is_none() && (options.interocular.is_some() || options.convergence.is_some() || options.toe_in) {
+            return Err(String::from("the eyes can only be set up for stereo renders"));
+        }
+        if options.interocular.is_some_and(|distance| distance < 0.0) {
+            return Err(String::from("the interocular distance must not be negative"));
+        }
+        if options.convergence.is_some_and(|distance| distance <= 0.0) {
+            return Err(String::from("the convergence distance must be above 0"));
+        }
+        if options.shadow_floor.is_some_and(|floor| !(0.0..=1.0).contains(&floor)) {
+            return Err(String::from("the shadow floor must be between 0 and 1"));
+        }
+        Ok(options)
+    }
+
+    //Gets the value following an option
+    fn value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a str, String> {
+        match value {
+            Some(value) => Ok(value),
+            None => Err(format!("'{}' needs a value", option)),
+        }
+    }
+
+    //Gets the number following an option
+    fn number<T: FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+        let value = Options::value(option, value)?;
+        value.parse().map_err(|_| format!("'{}' is not a valid value for '{}'", value, option))
+    }
+
+    //Reads a range of frames given as "first-last", or a single frame
+    fn frames(value: &str) -> Result<(usize, usize), String> {
+        let invalid = || format!("'{}' is not a valid frame range for '--frames'", value);
+        let (first, last) = match value.split_once('-') {
+            Some((first, last)) => (first.parse().map_err(|_| invalid())?, last.parse().map_err(|_| invalid())?),
+            None => {
+                let frame = value.parse().map_err(|_| invalid())?;
+                (frame, frame)
+            }
+        };
+        if first > last {
+            return Err(String::from("the first frame must not be after the last frame"));
+        }
+        Ok((first, last))
+    }
+
+    //Finds the file a frame of an animation is written to
+    //A run of #s in the path is replaced by the frame number padded to the same width,
+    //otherwise the frame number is added before the extension
+    pub fn frame_path(path: &str, frame: usize) -> String {
+        if let Some(start) = path.find('#') {
+            let width = path[start..].chars().take_while(|&character| character == '#').count();
+            return format!("{}{:0width$}{}", &path[..start], frame, &path[start + width..], width = width);
+        }
+        let name_start = path.rfind('/').map_or(0, |index| index + 1);
+        match path[name_start..].rfind('.') {
+            Some(dot) => format!("{}_{:04}{}", &path[..name_start + dot], frame, &path[name_start + dot..]),
+            None => format!("{}_{:04}", path, frame),
+        }
+    }
+
+    //Creates the stereo rig around a camera for stereo renders
+    pub fn stereo_rig(&self, camera: &Camera) -> Option<StereoRig> {
+        let layout = self.stereo?;
+        let convergence = self.convergence.unwrap_or(camera.focal_distance);
+        let mut rig = StereoRig::new(camera.clone(), self.interocular.unwrap_or(convergence / 30.0), convergence);
+        rig.layout = layout;
+        if self.toe_in {
+            rig.convergence = Convergence::ToeIn;
+        }
+        Some(rig)
+    }
+
+    //Overrides the camera and render settings which were given
+    pub fn apply(&self, camera: &mut Camera, settings: &mut RenderSettings) {
+        let aspect_ratio = camera.hsize as f32 / camera.vsize as f32;
+        let (width, height) = match (self.width, self.height) {
+            (Some(width), Some(height)) => (width, height),
+            (Some(width), None) => (width, ((width as f32 / aspect_ratio).round() as usize).max(1)),
+            (None, Some(height)) => (((height as f32 * aspect_ratio).round() as usize).max(1), height),
+            (None, None) => (camera.hsize as usize, camera.vsize as usize),
+        };
+        let fov = self.fov.unwrap_or_else(|| camera.field_of_view());
+        camera.resize(width, height, fov);
+        if let Some(integrator) = self.integrator {
+            settings.integrator = integrator;
+        }
+        if let Some(depth) = self.depth {
+            settings.max_depth = depth;
+        }
+        if let Some(samples) = self.samples {
+            settings.samples = samples;
+        }
+        if let Some(sampler) = self.sampler {
+            settings.sampler = sampler;
+        }
+        if let Some(filter) = self.filter {
+            settings.filter = filter;
+        }
+        if let Some(threshold) = self.threshold {
+            settings.adaptive_threshold = threshold;
+        }
+        if let Some(adaptive_depth) = self.adaptive_depth {
+            settings.adaptive_depth = adaptive_depth;
+        }
+        if let Some(threads) = self.threads {
+            settings.threads = threads;
+        }
+        if let Some(shadow_floor) = self.shadow_floor {
+            settings.shadow_floor = shadow_floor;
+        }
+        if self.opaque_shadows {
+            settings.transparent_shadows = false;
+        }
+        if let Some(seed) = self.seed {
+            settings.seed = seed;
+        }
+        settings.show_progress = !self.quiet;
+    }
+}
@@ -0,0 +1,46 @@
+use rand::rngs::StdRng;
+use rand::{Error, RngCore, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    //Random number generator used by the current thread, which is reseeded for every pixel
+    static GENERATOR: RefCell<StdRng> = RefCell::new(StdRng::seed_from_u64(0));
+}
+
+//Mixes a seed with the position of a pixel so every pixel gets its own stream of random numbers
+//Pixels are given the same numbers no matter which thread renders them
+pub fn pixel_seed(seed: u64, x: i32, y: i32) -> u64 {
+    let mut value = seed ^ ((x as u32 as u64) << 32 | (y as u32 as u64));
+    value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+//Restarts the current thread's random numbers from a seed
+pub fn reseed(seed: u64) {
+    GENERATOR.with(|generator| *generator.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+//Handle to the current thread's seeded random number generator
+//All random sampling goes through this so renders with the same seed are identical
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalRng;
+
+impl RngCore for LocalRng {
+    fn next_u32(&mut self) -> u32 {
+        GENERATOR.with(|generator| generator.borrow_mut().next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        GENERATOR.with(|generator| generator.borrow_mut().next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        GENERATOR.with(|generator| generator.borrow_mut().fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        GENERATOR.with(|generator| generator.borrow_mut().try_fill_bytes(dest))
+    }
+}
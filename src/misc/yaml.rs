@@ -0,0 +1,387 @@
+use std::error::Error;
+use std::fmt;
+
+//A ParseError stores the line a problem was found on so malformed files are easy to fix
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    //Creates a new ParseError
+    pub fn new(line: usize, message: &str) -> ParseError {
+        ParseError {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+//The contents of a node are either plain text, a list of nodes or a list of keyed nodes
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Scalar(String),
+    List(Vec<Node>),
+    Map(Vec<(String, Node)>),
+}
+
+//A Node is a value in a YAML-like document along with the line it starts on
+#[derive(Debug, PartialEq, Clone)]
+pub struct Node {
+    pub value: Value,
+    pub line: usize,
+}
+
+//A non blank line with its comment removed
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+impl Node {
+    //Creates a new Node
+    pub fn new(value: Value, line: usize) -> Node {
+        Node {
+            value,
+            line,
+        }
+    }
+
+    //Parses a document made from a subset of YAML
+    //Supports block maps, block lists, inline lists ([a, b]), inline maps ({a: b}), quoted text and # comments
+    pub fn parse(text: &str) -> Result<Node, ParseError> {
+        let mut lines = vec![];
+        for (index, raw) in text.lines().enumerate() {
+            if raw.contains('\t') {
+                return Err(ParseError::new(index + 1, "tabs cannot be used for indentation"));
+            }
+            let without_comment = Node::strip_comment(raw);
+            let trimmed = without_comment.trim_end();
+            if trimmed.trim().is_empty() || trimmed.trim() == "---" {
+                continue;
+            }
+            let indent = trimmed.len() - trimmed.trim_start().len();
+            lines.push(Line {
+                number: index + 1,
+                indent,
+                text: trimmed.trim_start().to_string(),
+            });
+        }
+        if lines.is_empty() {
+            return Ok(Node::new(Value::List(vec![]), 1));
+        }
+        let mut position = 0;
+        let indent = lines[0].indent;
+        let node = Node::parse_block(&mut lines, &mut position, indent)?;
+        if position < lines.len() {
+            return Err(ParseError::new(lines[position].number, "unexpected indentation"));
+        }
+        Ok(node)
+    }
+
+    //Removes a comment from the end of a line, ignoring # inside quotes
+    fn strip_comment(line: &str) -> &str {
+        let mut in_quotes = false;
+        let mut previous = ' ';
+        for (index, character) in line.char_indices() {
+            if character == '"' {
+                in_quotes = !in_quotes;
+            }
+            else if character == '#' && !in_quotes && previous.is_whitespace() {
+                return &line[..index];
+            }
+            previous = character;
+        }
+        line
+    }
+
+    //Parses every line starting at the given indentation as a single list or map
+    fn parse_block(lines: &mut Vec<Line>, position: &mut usize, indent: usize) -> Result<Node, ParseError> {
+        if lines[*position].text == "-" || lines[*position].text.starts_with("- ") {
+            Node::parse_list(lines, position, indent)
+        }
+        else {
+            Node::parse_map(lines, position, indent)
+        }
+    }
+
+    //Parses a block list where each item starts with a dash
+    fn parse_list(lines: &mut Vec<Line>, position: &mut usize, indent: usize) -> Result<Node, ParseError> {
+        let start = lines[*position].number;
+        let mut items = vec![];
+        while *position < lines.len() && lines[*position].indent == indent {
+            let line = &lines[*position];
+            if !(line.text == "-" || line.text.starts_with("- ")) {
+                return Err(ParseError::new(line.number, "expected a list item starting with '-'"));
+            }
+            let number = line.number;
+            let rest = line.text[1..].trim_start().to_string();
+            if rest.is_empty() {
+                //The item is written on the following lines
+                *position += 1;
+                if *position < lines.len() && lines[*position].indent > indent {
+                    let child_indent = lines[*position].indent;
+                    items.push(Node::parse_block(lines, position, child_indent)?);
+                }
+                else {
+                    items.push(Node::new(Value::Scalar(String::new()), number));
+                }
+            }
+            else if rest.starts_with('[') || rest.starts_with('{') || !Node::is_map_entry(&rest) {
+                items.push(Node::parse_inline(&rest, number)?);
+                *position += 1;
+            }
+            else {
+                //The item is a map whose first key shares the line with the dash
+                let child_indent = indent + (line.text.len() - rest.len());
+                lines[*position].indent = child_indent;
+                lines[*position].text = rest;
+                items.push(Node::parse_map(lines, position, child_indent)?);
+            }
+        }
+        Ok(Node::new(Value::List(items), start))
+    }
+
+    //Parses a block map of key: value lines
+    fn parse_map(lines: &mut Vec<Line>, position: &mut usize, indent: usize) -> Result<Node, ParseError> {
+        let start = lines[*position].number;
+        let mut entries: Vec<(String, Node)> = vec![];
+        while *position < lines.len() && lines[*position].indent == indent {
+            let number = lines[*position].number;
+            let text = lines[*position].text.clone();
+            if text == "-" || text.starts_with("- ") {
+                return Err(ParseError::new(number, "unexpected list item inside a map"));
+            }
+            let (key, rest) = match Node::split_entry(&text) {
+                Some(entry) => entry,
+                None => return Err(ParseError::new(number, &format!("expected 'key: value' but found '{}'", text))),
+            };
+            if entries.iter().any(|(existing, _)| existing == &key) {
+                return Err(ParseError::new(number, &format!("duplicate key '{}'", key)));
+            }
+            *position += 1;
+            let value = if !rest.is_empty() {
+                Node::parse_inline(&rest, number)?
+            }
+            else if *position < lines.len() && lines[*position].indent > indent {
+                //Block values report the line of their key
+                let child_indent = lines[*position].indent;
+                let mut block = Node::parse_block(lines, position, child_indent)?;
+                block.line = number;
+                block
+            }
+            else if *position < lines.len() && lines[*position].indent == indent && lines[*position].text.starts_with('-') {
+                //Lists may start at the same indentation as their key
+                let mut block = Node::parse_list(lines, position, indent)?;
+                block.line = number;
+                block
+            }
+            else {
+                Node::new(Value::Scalar(String::new()), number)
+            };
+            entries.push((key, value));
+        }
+        if *position < lines.len() && lines[*position].indent > indent {
+            return Err(ParseError::new(lines[*position].number, "unexpected indentation"));
+        }
+        Ok(Node::new(Value::Map(entries), start))
+    }
+
+    //Checks if text outside of quotes and brackets looks like "key: value"
+    fn is_map_entry(text: &str) -> bool {
+        Node::split_entry(text).is_some()
+    }
+
+    //Splits "key: value" into its key and value text
+    fn split_entry(text: &str) -> Option<(String, String)> {
+        if text.starts_with('"') || text.starts_with('[') || text.starts_with('{') {
+            return None;
+        }
+        let index = text.find(": ").or_else(|| if text.ends_with(':') { Some(text.len() - 1) } else { None })?;
+        let key = text[..index].trim();
+        if key.is_empty() || key.contains(' ') {
+            return None;
+        }
+        Some((key.to_string(), text[index + 1..].trim().to_string()))
+    }
+
+    //Parses a value written on a single line
+    fn parse_inline(text: &str, line: usize) -> Result<Node, ParseError> {
+        let characters: Vec<char> = text.chars().collect();
+        let mut index = 0;
+        let node = Node::parse_flow(&characters, &mut index, line)?;
+        Node::skip_spaces(&characters, &mut index);
+        if index < characters.len() {
+            return Err(ParseError::new(line, &format!("unexpected text '{}'", characters[index..].iter().collect::<String>())));
+        }
+        Ok(node)
+    }
+
+    fn skip_spaces(characters: &[char], index: &mut usize) {
+        while *index < characters.len() && characters[*index].is_whitespace() {
+            *index += 1;
+        }
+    }
+
+    //Parses an inline list, inline map or scalar
+    fn parse_flow(characters: &[char], index: &mut usize, line: usize) -> Result<Node, ParseError> {
+        Node::skip_spaces(characters, index);
+        if *index >= characters.len() {
+            return Err(ParseError::new(line, "expected a value"));
+        }
+        match characters[*index] {
+            '[' => {
+                *index += 1;
+                let mut items = vec![];
+                loop {
+                    Node::skip_spaces(characters, index);
+                    if *index >= characters.len() {
+                        return Err(ParseError::new(line, "unclosed '['"));
+                    }
+                    if characters[*index] == ']' {
+                        *index += 1;
+                        break;
+                    }
+                    items.push(Node::parse_flow(characters, index, line)?);
+                    Node::skip_spaces(characters, index);
+                    if *index < characters.len() && characters[*index] == ',' {
+                        *index += 1;
+                    }
+                    else if *index >= characters.len() || characters[*index] != ']' {
+                        return Err(ParseError::new(line, "expected ',' or ']' in list"));
+                    }
+                }
+                Ok(Node::new(Value::List(items), line))
+            }
+            '{' => {
+                *index += 1;
+                let mut entries = vec![];
+                loop {
+                    Node::skip_spaces(characters, index);
+                    if *index >= characters.len() {
+                        return Err(ParseError::new(line, "unclosed '{'"));
+                    }
+                    if characters[*index] == '}' {
+                        *index += 1;
+                        break;
+                    }
+                    let key = Node::parse_plain(characters, index, true);
+                    if key.is_empty() || *index >= characters.len() || characters[*index] != ':' {
+                        return Err(ParseError::new(line, "expected 'key: value' in map"));
+                    }
+                    *index += 1;
+                    let value = Node::parse_flow(characters, index, line)?;
+                    entries.push((key, value));
+                    Node::skip_spaces(characters, index);
+                    if *index < characters.len() && characters[*index] == ',' {
+                        *index += 1;
+                    }
+                    else if *index >= characters.len() || characters[*index] != '}' {
+                        return Err(ParseError::new(line, "expected ',' or '}' in map"));
+                    }
+                }
+                Ok(Node::new(Value::Map(entries), line))
+            }
+            '"' => {
+                *index += 1;
+                let mut result = String::new();
+                while *index < characters.len() && characters[*index] != '"' {
+                    result.push(characters[*index]);
+                    *index += 1;
+                }
+                if *index >= characters.len() {
+                    return Err(ParseError::new(line, "unclosed quote"));
+                }
+                *index += 1;
+                Ok(Node::new(Value::Scalar(result), line))
+            }
+            _ => Ok(Node::new(Value::Scalar(Node::parse_plain(characters, index, false)), line)),
+        }
+    }
+
+    //Reads unquoted text up to the next separator
+    fn parse_plain(characters: &[char], index: &mut usize, is_key: bool) -> String {
+        let mut result = String::new();
+        while *index < characters.len() {
+            let character = characters[*index];
+            if character == ',' || character == ']' || character == '}' || (is_key && character == ':') {
+                break;
+            }
+            result.push(character);
+            *index += 1;
+        }
+        result.trim().to_string()
+    }
+
+    //Finds the value of a key if the node is a map
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        match &self.value {
+            Value::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, node)| node),
+            _ => None,
+        }
+    }
+
+    //Gets the text of a scalar node
+    pub fn as_str(&self) -> Result<&str, ParseError> {
+        match &self.value {
+            Value::Scalar(text) => Ok(text),
+            _ => Err(ParseError::new(self.line, "expected a single value")),
+        }
+    }
+
+    //Gets the number stored in a scalar node
+    pub fn as_f32(&self) -> Result<f32, ParseError> {
+        let text = self.as_str()?;
+        text.parse::<f32>().map_err(|_| ParseError::new(self.line, &format!("expected a number but found '{}'", text)))
+    }
+
+    //Gets the whole number stored in a scalar node
+    pub fn as_usize(&self) -> Result<usize, ParseError> {
+        let text = self.as_str()?;
+        text.parse::<usize>().map_err(|_| ParseError::new(self.line, &format!("expected a whole number but found '{}'", text)))
+    }
+
+    //Gets the true or false value stored in a scalar node
+    pub fn as_bool(&self) -> Result<bool, ParseError> {
+        match self.as_str()? {
+            "true" | "yes" | "on" => Ok(true),
+            "false" | "no" | "off" => Ok(false),
+            text => Err(ParseError::new(self.line, &format!("expected true or false but found '{}'", text))),
+        }
+    }
+
+    //Gets the items of a list node
+    pub fn as_list(&self) -> Result<&Vec<Node>, ParseError> {
+        match &self.value {
+            Value::List(items) => Ok(items),
+            _ => Err(ParseError::new(self.line, "expected a list")),
+        }
+    }
+
+    //Gets the entries of a map node
+    pub fn as_map(&self) -> Result<&Vec<(String, Node)>, ParseError> {
+        match &self.value {
+            Value::Map(entries) => Ok(entries),
+            _ => Err(ParseError::new(self.line, "expected a map of 'key: value' entries")),
+        }
+    }
+
+    //Gets a list of exactly three numbers
+    pub fn as_triple(&self) -> Result<(f32, f32, f32), ParseError> {
+        let items = self.as_list()?;
+        if items.len() != 3 {
+            return Err(ParseError::new(self.line, &format!("expected 3 numbers but found {}", items.len())));
+        }
+        Ok((items[0].as_f32()?, items[1].as_f32()?, items[2].as_f32()?))
+    }
+}
@@ -0,0 +1,164 @@
+use crate::core::matrix::Matrix4x4;
+use crate::core::vector::Vec4;
+use crate::misc::utils::*;
+use crate::ray_tracing::ray::Ray;
+
+//An axis aligned box which encloses an object and is used to skip objects a ray cannot hit
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundingBox {
+    pub min: Vec4,
+    pub max: Vec4,
+}
+
+impl BoundingBox {
+    //Creates a new BoundingBox from its minimum and maximum corners
+    pub fn new(min: Vec4, max: Vec4) -> BoundingBox {
+        BoundingBox {
+            min,
+            max,
+        }
+    }
+
+    //Creates a box which contains nothing
+    pub fn empty() -> BoundingBox {
+        BoundingBox {
+            min: Vec4(f32::INFINITY, f32::INFINITY, f32::INFINITY, 1.0),
+            max: Vec4(-f32::INFINITY, -f32::INFINITY, -f32::INFINITY, 1.0),
+        }
+    }
+
+    //Creates a box which contains everything (used for planes and uncapped objects)
+    pub fn infinite() -> BoundingBox {
+        BoundingBox {
+            min: Vec4(-f32::INFINITY, -f32::INFINITY, -f32::INFINITY, 1.0),
+            max: Vec4(f32::INFINITY, f32::INFINITY, f32::INFINITY, 1.0),
+        }
+    }
+
+    //Checks if the box has no contents
+    pub fn is_empty(&self) -> bool {
+        self.min.0 > self.max.0 || self.min.1 > self.max.1 || self.min.2 > self.max.2
+    }
+
+    //Checks if every side of the box is finite
+    pub fn is_finite(&self) -> bool {
+        self.min.0.is_finite() && self.min.1.is_finite() && self.min.2.is_finite()
+            && self.max.0.is_finite() && self.max.1.is_finite() && self.max.2.is_finite()
+    }
+
+    //Grows the box to contain a point
+    pub fn add_point(&mut self, point: &Vec4) {
+        self.min = Vec4(self.min.0.min(point.0), self.min.1.min(point.1), self.min.2.min(point.2), 1.0);
+        self.max = Vec4(self.max.0.max(point.0), self.max.1.max(point.1), self.max.2.max(point.2), 1.0);
+    }
+
+    //Grows the box to contain another box
+    pub fn merge(&mut self, other: &BoundingBox) {
+        if !other.is_empty() {
+            self.add_point(&other.min);
+            self.add_point(&other.max);
+        }
+    }
+
+    //Checks if a point lies within the box
+    pub fn contains_point(&self, point: &Vec4) -> bool {
+        self.min.0 <= point.0 && point.0 <= self.max.0
+            && self.min.1 <= point.1 && point.1 <= self.max.1
+            && self.min.2 <= point.2 && point.2 <= self.max.2
+    }
+
+    //Checks if another box lies entirely within the box
+    pub fn contains_box(&self, other: &BoundingBox) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    //Finds the center of the box
+    pub fn centroid(&self) -> Vec4 {
+        Vec4(
+            (self.min.0 + self.max.0) / 2.0,
+            (self.min.1 + self.max.1) / 2.0,
+            (self.min.2 + self.max.2) / 2.0,
+            1.0,
+        )
+    }
+
+    //Finds the axis (0 = x, 1 = y, 2 = z) along which the box is longest
+    pub fn longest_axis(&self) -> usize {
+        let x = self.max.0 - self.min.0;
+        let y = self.max.1 - self.min.1;
+        let z = self.max.2 - self.min.2;
+        if x >= y && x >= z {
+            0
+        }
+        else if y >= z {
+            1
+        }
+        else {
+            2
+        }
+    }
+
+    //Transforms the box and finds a new axis aligned box which encloses the result
+    pub fn transform(&self, matrix: &Matrix4x4) -> BoundingBox {
+        if self.is_empty() {
+            return BoundingBox::empty();
+        }
+        //Infinite sides cannot be transformed without producing NaN values
+        if !self.is_finite() {
+            return BoundingBox::infinite();
+        }
+        let corners = [
+            Vec4(self.min.0, self.min.1, self.min.2, 1.0),
+            Vec4(self.min.0, self.min.1, self.max.2, 1.0),
+            Vec4(self.min.0, self.max.1, self.min.2, 1.0),
+            Vec4(self.min.0, self.max.1, self.max.2, 1.0),
+            Vec4(self.max.0, self.min.1, self.min.2, 1.0),
+            Vec4(self.max.0, self.min.1, self.max.2, 1.0),
+            Vec4(self.max.0, self.max.1, self.min.2, 1.0),
+            Vec4(self.max.0, self.max.1, self.max.2, 1.0),
+        ];
+        let mut result = BoundingBox::empty();
+        for corner in corners.iter() {
+            result.add_point(&(matrix * corner));
+        }
+        result
+    }
+
+    //Checks if a ray passes through the box
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let (xmin, xmax) = BoundingBox::check_axis(ray.origin.0, ray.direction.0, self.min.0, self.max.0);
+        let (ymin, ymax) = BoundingBox::check_axis(ray.origin.1, ray.direction.1, self.min.1, self.max.1);
+        let (zmin, zmax) = BoundingBox::check_axis(ray.origin.2, ray.direction.2, self.min.2, self.max.2);
+
+        let tmin = xmin.max(ymin).max(zmin);
+        let tmax = xmax.min(ymax).min(zmax);
+
+        tmin <= tmax
+    }
+
+    //Finds the minimum and maximum values of where a given ray crosses the slabs of one axis
+    fn check_axis(origin: f32, direction: f32, min: f32, max: f32) -> (f32, f32) {
+        //A ray parallel to the slabs either always or never lies between them
+        if direction.abs() < EPSILON_BUMP {
+            if origin < min - EPSILON_BUMP || origin > max + EPSILON_BUMP {
+                return (f32::INFINITY, -f32::INFINITY);
+            }
+            return (-f32::INFINITY, f32::INFINITY);
+        }
+
+        let t1 = (min - origin) / direction;
+        let t2 = (max - origin) / direction;
+
+        //Pads the range slightly so rays grazing flat boxes (such as triangles) are not rejected
+        let padding = EPSILON_BUMP / direction.abs();
+        if t1 > t2 {
+            (t2 - padding, t1 + padding)
+        }
+        else {
+            (t1 - padding, t2 + padding)
+        }
+    }
+}
@@ -5,6 +5,7 @@ use crate::materials::material::*;
 use crate::ray_tracing::ray::Ray;
 use crate::ray_tracing::intersection::Intersection;
 use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
 use crate::misc::utils::*;
 use std::any::Any;
 
@@ -198,6 +199,12 @@ impl Object for Cone {
         normal_to_world(&self.parent_inverses, &world_normal.normalize())
     }
 
+    //Finds the bounds of a cone, whose radius at any y is the absolute value of y
+    fn bounds(&self) -> BoundingBox {
+        let limit = self.minimum.abs().max(self.maximum.abs());
+        BoundingBox::new(Vec4(-limit, self.minimum, -limit, 1.0), Vec4(limit, self.maximum, limit, 1.0)).transform(&self.transform)
+    }
+
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
         &self.parent_inverses
     }
@@ -216,7 +223,7 @@ impl Object for Cone {
     fn add_to_group(mut self, group: &mut Group) {
         self.push_parent_inverse(group.get_inverse().clone());
         self.set_parent_material(&group.material);
-        group.objects.push(Box::new(self));
+        group.push(Box::new(self));
     }
 
     fn eq(&self, other: &dyn Object) -> bool {
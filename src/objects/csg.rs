@@ -4,6 +4,7 @@ use crate::objects::object::*;
 use crate::ray_tracing::intersection::Intersection;
 use crate::materials::material::*;
 use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
 use crate::objects::sphere::Sphere;
 use crate::objects::cube::Cube;
 use crate::ray_tracing::ray::Ray;
@@ -176,7 +177,7 @@ impl Object for CSG {
                 new_intersection = Intersection::new(
                     intersection.t,
                     Ray::position(&transformed_ray, intersection.t),
-                    (intersection.object).normal(&Ray::position(&ray, intersection.t), None, None),
+                    (intersection.object).normal_at(&Ray::position(ray, intersection.t), None, None, ray.time),
                     intersection.object,
                 );
             }
@@ -184,7 +185,7 @@ impl Object for CSG {
                 new_intersection = Intersection::new(
                     intersection.t,
                     Ray::position(&transformed_ray, intersection.t),
-                    (intersection.object).normal(&Ray::position(&ray, intersection.t), intersection.u, intersection.v),
+                    (intersection.object).normal_at(&Ray::position(ray, intersection.t), intersection.u, intersection.v, ray.time),
                     intersection.object,
                 );
             }
@@ -204,6 +205,22 @@ impl Object for CSG {
         panic!("Cannot find the normal of a CSG");
     }
 
+    //Finds the bounds of both children transformed into the space of the csg's parent
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        for object in &self.objects {
+            bounds.merge(&object.bounds());
+        }
+        bounds.transform(&self.transform)
+    }
+
+    //Divides any groups used as arguments of the csg
+    fn divide(&mut self, threshold: usize) {
+        for object in &mut self.objects {
+            object.divide(threshold);
+        }
+    }
+
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
         &self.parent_inverses
     }
@@ -223,7 +240,7 @@ impl Object for CSG {
     fn add_to_group(mut self, group: &mut Group) {
         self.push_parent_inverse(group.get_inverse().clone());
         self.set_parent_material(&group.material);
-        group.objects.push(Box::new(self));
+        group.push(Box::new(self));
     }
 
     fn eq(&self, other: &dyn Object) -> bool {
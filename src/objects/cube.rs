@@ -4,6 +4,8 @@ use crate::objects::object::*;
 use crate::materials::material::*;
 use crate::ray_tracing::ray::Ray;
 use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
+use crate::objects::motion::Motion;
 use crate::ray_tracing::intersection::Intersection;
 use crate::misc::utils::*;
 use std::any::Any;
@@ -15,6 +17,7 @@ pub struct Cube {
     pub material: Material,
     pub parent_inverses: Vec<Matrix4x4>,
     pub parent_material: Option<Material>,
+    pub motion: Option<Motion>,
 }
 
 impl Cube {
@@ -26,6 +29,7 @@ impl Cube {
             material,
             parent_inverses: vec![],
             parent_material: None,
+            motion: None,
         }
     }
 
@@ -37,6 +41,7 @@ impl Cube {
             material: Material::default(),
             parent_inverses: vec![],
             parent_material: None,
+            motion: None,
         }
     }
 
@@ -65,6 +70,27 @@ impl Cube {
 
         (tmin, tmax)
     }
+
+    //Finds the normal on a given point on a cube with a given inverse transform
+    fn normal_with_inverse(&self, world_point: &Vec4, inverse: &Matrix4x4) -> Vec4 {
+        let group_point = world_to_object(&self.parent_inverses, world_point);
+        let transformed_point = inverse * group_point;
+        let coords = [transformed_point.0.abs(), transformed_point.1.abs(), transformed_point.2.abs()];
+        let max_coord = coords.iter().fold(-f32::INFINITY, |a, &b| a.max(b));
+        let result;
+        if max_coord == transformed_point.0.abs() {
+            result = Vec4(transformed_point.0, 0.0, 0.0, 0.0);
+        }
+        else if max_coord == transformed_point.1.abs() {
+            result = Vec4(0.0, transformed_point.1, 0.0, 0.0);
+        }
+        else {
+            result = Vec4(0.0, 0.0, transformed_point.2, 0.0);
+        }
+        let mut world_normal = &inverse.transpose() * result;
+        world_normal.3 = 0.0;
+        normal_to_world(&self.parent_inverses, &world_normal.normalize())
+    }
 }
 
 impl Object for Cube {
@@ -78,9 +104,14 @@ impl Object for Cube {
         &self.inverse
     }
 
+    fn get_inverse_at(&self, time: f32) -> Matrix4x4 {
+        Motion::inverse_at(&self.motion, &self.transform, &self.inverse, time).unwrap_or_else(|| self.inverse.clone())
+    }
+
     //Intersects a ray with a cube
     fn intersect(&self, ray: &Ray) -> Option<Vec<Intersection>> {
-        let transformed_ray = Ray::transform(ray, &self.inverse);
+        let moved_inverse = Motion::inverse_at(&self.motion, &self.transform, &self.inverse, ray.time);
+        let transformed_ray = Ray::transform(ray, moved_inverse.as_ref().unwrap_or(&self.inverse));
         let (xmin, xmax) = Cube::check_axis(transformed_ray.origin.0, transformed_ray.direction.0);
         let (ymin, ymax) = Cube::check_axis(transformed_ray.origin.1, transformed_ray.direction.1);
         let (zmin, zmax) = Cube::check_axis(transformed_ray.origin.2, transformed_ray.direction.2);
@@ -100,13 +131,13 @@ impl Object for Cube {
                     Intersection::new(
                         tmin,
                         Ray::position(&ray, tmin),
-                        self.normal(&Ray::position(&ray, tmin), None, None),
+                        self.normal_at(&Ray::position(ray, tmin), None, None, ray.time),
                         self,
                     ),
                     Intersection::new(
                         tmax,
                         Ray::position(&ray, tmax),
-                        self.normal(&Ray::position(&ray, tmax), None, None),
+                        self.normal_at(&Ray::position(ray, tmax), None, None, ray.time),
                         self,
                     ),
                 ]
@@ -116,23 +147,19 @@ impl Object for Cube {
 
     //Finds the normal on a given point on a cube
     fn normal(&self, world_point: &Vec4, _u: Option<f32>, _v: Option<f32>) -> Vec4 {
-        let group_point = world_to_object(&self.parent_inverses, world_point);
-        let transformed_point = &self.inverse * group_point;
-        let coords = [transformed_point.0.abs(), transformed_point.1.abs(), transformed_point.2.abs()];
-        let max_coord = coords.iter().fold(-f32::INFINITY, |a, &b| a.max(b));
-        let result;
-        if max_coord == transformed_point.0.abs() {
-            result = Vec4(transformed_point.0, 0.0, 0.0, 0.0);
-        }
-        else if max_coord == transformed_point.1.abs() {
-            result = Vec4(0.0, transformed_point.1, 0.0, 0.0);
-        }
-        else {
-            result = Vec4(0.0, 0.0, transformed_point.2, 0.0);
-        }
-        let mut world_normal = &self.inverse.transpose() * result;
-        world_normal.3 = 0.0;
-        normal_to_world(&self.parent_inverses, &world_normal.normalize())
+        self.normal_with_inverse(world_point, &self.inverse)
+    }
+
+    //Finds the normal on a given point on a cube where it is at a time
+    fn normal_at(&self, world_point: &Vec4, _u: Option<f32>, _v: Option<f32>, time: f32) -> Vec4 {
+        let moved_inverse = Motion::inverse_at(&self.motion, &self.transform, &self.inverse, time);
+        self.normal_with_inverse(world_point, moved_inverse.as_ref().unwrap_or(&self.inverse))
+    }
+
+    //Finds the bounds of a cube
+    fn bounds(&self) -> BoundingBox {
+        let local = BoundingBox::new(Vec4(-1.0, -1.0, -1.0, 1.0), Vec4(1.0, 1.0, 1.0, 1.0));
+        Motion::bounds(&self.motion, &self.transform, &local)
     }
 
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
@@ -154,7 +181,7 @@ impl Object for Cube {
     fn add_to_group(mut self, group: &mut Group) {
         self.push_parent_inverse(group.get_inverse().clone());
         self.set_parent_material(&group.material);
-        group.objects.push(Box::new(self));
+        group.push(Box::new(self));
     }
 
     fn eq(&self, other: &dyn Object) -> bool {
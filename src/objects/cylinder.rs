@@ -5,6 +5,7 @@ use crate::materials::material::*;
 use crate::ray_tracing::ray::Ray;
 use crate::ray_tracing::intersection::Intersection;
 use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
 use crate::misc::utils::*;
 use std::any::Any;
 
@@ -177,6 +178,11 @@ impl Object for Cylinder {
         normal_to_world(&self.parent_inverses, &world_normal)
     }
 
+    //Finds the bounds of a cylinder (infinite along y if the cylinder is not truncated)
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Vec4(-1.0, self.minimum, -1.0, 1.0), Vec4(1.0, self.maximum, 1.0, 1.0)).transform(&self.transform)
+    }
+
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
         &self.parent_inverses
     }
@@ -196,7 +202,7 @@ impl Object for Cylinder {
     fn add_to_group(mut self, group: &mut Group) {
         self.push_parent_inverse(group.get_inverse().clone());
         self.set_parent_material(&group.material);
-        group.objects.push(Box::new(self));
+        group.push(Box::new(self));
     }
 
     fn eq(&self, other: &dyn Object) -> bool {
@@ -0,0 +1,100 @@
+use crate::core::matrix::Matrix4x4;
+use crate::core::vector::Vec4;
+use crate::objects::object::*;
+use crate::materials::material::*;
+use crate::ray_tracing::ray::Ray;
+use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
+use crate::ray_tracing::intersection::Intersection;
+use std::any::Any;
+
+//A Disk is a one-sided circle of radius 1 in the xz plane, facing along y
+//Rays reaching it from below pass straight through, so it can show a surface which is only seen from the front
+#[derive(Debug, PartialEq, Clone)]
+pub struct Disk {
+    pub transform: Matrix4x4,
+    pub inverse: Matrix4x4,
+    pub material: Material,
+    pub parent_inverses: Vec<Matrix4x4>,
+    pub parent_material: Option<Material>,
+}
+
+impl Disk {
+    //Instantiates a Disk with the given transform and material
+    pub fn new(transform: Matrix4x4, material: Material) -> Disk {
+        Disk {
+            inverse: transform.inverse().unwrap(),
+            transform,
+            material,
+            parent_inverses: vec![],
+            parent_material: None,
+        }
+    }
+}
+
+impl Object for Disk {
+    //Returns the disk material
+    fn get_material(&self) -> &Material {
+        &self.material
+    }
+
+    //Returns the disk matrix
+    fn get_inverse(&self) -> &Matrix4x4 {
+        &self.inverse
+    }
+
+    //Intersects a ray with the front of a disk
+    fn intersect(&self, ray: &Ray) -> Option<Vec<Intersection<'_>>> {
+        let transformed_ray = Ray::transform(ray, &self.inverse);
+        if transformed_ray.direction.1 >= 0.0 {
+            return None;
+        }
+        let t = -transformed_ray.origin.1 / transformed_ray.direction.1;
+        let x = transformed_ray.origin.0 + t * transformed_ray.direction.0;
+        let z = transformed_ray.origin.2 + t * transformed_ray.direction.2;
+        if x * x + z * z > 1.0 {
+            return None;
+        }
+        let i = Intersection::new(t, Ray::position(ray, t), self.normal(&Ray::position(ray, t), None, None), self);
+        Some(vec![i])
+    }
+
+    //The normal of a disk always points along its y axis
+    fn normal(&self, _world_point: &Vec4, _u: Option<f32>, _v: Option<f32>) -> Vec4 {
+        let mut result = &self.inverse.transpose() * Vec4::new(0.0, 1.0, 0.0, 0.0);
+        result.3 = 0.0;
+        normal_to_world(&self.parent_inverses, &result.normalize())
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Vec4(-1.0, 0.0, -1.0, 1.0), Vec4(1.0, 0.0, 1.0, 1.0)).transform(&self.transform)
+    }
+
+    fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
+        &self.parent_inverses
+    }
+
+    fn push_parent_inverse(&mut self, inverse: Matrix4x4) {
+        self.parent_inverses.push(inverse);
+    }
+
+    fn get_parent_material(&self) -> &Option<Material> {
+        &self.parent_material
+    }
+
+    fn set_parent_material(&mut self, material: &Material) {
+        self.parent_material = Some(material.clone());
+    }
+
+    fn add_to_group(mut self, group: &mut Group) {
+        self.push_parent_inverse(group.get_inverse().clone());
+        self.set_parent_material(&group.material);
+        group.push(Box::new(self));
+    }
+
+    fn eq(&self, other: &dyn Object) -> bool {
+        other.as_any().downcast_ref::<Self>().is_some_and(|x| x == self)
+    }
+
+    fn as_any(&self) -> &dyn Any { self }
+}
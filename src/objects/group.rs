@@ -4,7 +4,13 @@ use crate::objects::object::*;
 use crate::materials::material::*;
 use crate::ray_tracing::ray::Ray;
 use crate::ray_tracing::intersection::Intersection;
+use crate::objects::bounds::BoundingBox;
+use crate::objects::motion::Motion;
 use std::any::Any;
+use std::sync::OnceLock;
+
+//Groups with more children than this are split when building a bounding volume hierarchy
+pub const BVH_THRESHOLD: usize = 4;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Group {
@@ -14,6 +20,10 @@ pub struct Group {
     pub objects: Vec<Box<dyn Object>>,
     parent_inverses: Vec<Matrix4x4>,
     pub parent_material: Option<Material>,
+    //Bounds of the children in group space, computed on the first intersection and cleared when children are added
+    local_bounds: OnceLock<BoundingBox>,
+    //Moving groups shade with the normals of where they start when they are inside a transformed group
+    pub motion: Option<Motion>,
 }
 
 impl Group {
@@ -26,6 +36,8 @@ impl Group {
             objects: vec![],
             parent_inverses: vec![],
             parent_material: None,
+            local_bounds: OnceLock::new(),
+            motion: None,
         };
         group
     }
@@ -39,35 +51,114 @@ impl Group {
             objects: vec![],
             parent_inverses: vec![],
             parent_material: None,
+            local_bounds: OnceLock::new(),
+            motion: None,
         }
     }
-}
 
-impl<'a> Object for Group {
-    //Returns the group material
-    fn get_material(&self) -> &Material {
-        &self.material
+    //Finds the box enclosing every child in group space
+    pub fn local_bounds(&self) -> &BoundingBox {
+        self.local_bounds.get_or_init(|| {
+            let mut bounds = BoundingBox::empty();
+            for object in &self.objects {
+                bounds.merge(&object.bounds());
+            }
+            bounds
+        })
     }
 
-    //Returns the group matrix
-    fn get_inverse(&self) -> &Matrix4x4 {
-        &self.inverse
+    //Adds a child to the group, clearing the cached bounds so they include it
+    pub fn push(&mut self, object: Box<dyn Object>) {
+        self.objects.push(object);
+        self.reset_bounds();
     }
 
-    //Intersects a ray with a group
-    fn intersect(&self, ray: &Ray) -> Option<Vec<Intersection>> {
+    //Clears the cached bounds so they are found again on the next intersection
+    //Needed after changing the children (or their transforms) in place through the objects field
+    pub fn reset_bounds(&mut self) {
+        self.local_bounds = OnceLock::new();
+    }
+
+    //Splits the children into two subgroups along the longest axis of their centers
+    //Children with infinite bounds (such as planes) cannot be split and stay in the group
+    fn split_children(&mut self) -> Option<(Group, Group)> {
+        let mut finite: Vec<(Vec4, Box<dyn Object>)> = vec![];
+        let mut unbounded = vec![];
+        for object in self.objects.drain(..) {
+            let bounds = object.bounds();
+            if bounds.is_finite() {
+                finite.push((bounds.centroid(), object));
+            }
+            else {
+                unbounded.push(object);
+            }
+        }
+        self.objects = unbounded;
+
+        let mut centroid_bounds = BoundingBox::empty();
+        for (centroid, _) in &finite {
+            centroid_bounds.add_point(centroid);
+        }
+        let axis = centroid_bounds.longest_axis();
+        let coordinate = |point: &Vec4| match axis {
+            0 => point.0,
+            1 => point.1,
+            _ => point.2,
+        };
+        finite.sort_by(|a, b| coordinate(&a.0).partial_cmp(&coordinate(&b.0)).unwrap());
+
+        let right: Vec<Box<dyn Object>> = finite.split_off(finite.len() / 2).into_iter().map(|(_, object)| object).collect();
+        let left: Vec<Box<dyn Object>> = finite.into_iter().map(|(_, object)| object).collect();
+        if left.len() <= 1 || right.is_empty() {
+            self.objects.extend(left);
+            self.objects.extend(right);
+            None
+        }
+        else {
+            Some((self.subgroup(left), self.subgroup(right)))
+        }
+    }
+
+    //Creates an untransformed subgroup holding some of the children of this group
+    //Since the subgroup has an identity transform the parent inverses of its children stay valid
+    fn subgroup(&self, objects: Vec<Box<dyn Object>>) -> Group {
+        let mut parent_inverses = vec![self.inverse.clone()];
+        parent_inverses.extend(self.parent_inverses.iter().cloned());
+        Group {
+            transform: Matrix4x4::identity(),
+            inverse: Matrix4x4::identity(),
+            material: self.material.clone(),
+            parent_material: objects[0].get_parent_material().clone(),
+            objects,
+            parent_inverses,
+            local_bounds: OnceLock::new(),
+            motion: None,
+        }
+    }
+
+    //Intersects a ray with a group which is not moving
+    //Untransformed groups keep the intersections of their children, which were found in the same space
+    fn intersect_still(&self, ray: &Ray) -> Option<Vec<Intersection<'_>>> {
         let transformed_ray = Ray::transform(ray, &self.inverse);
+        //Skips every child if the ray misses the box around them
+        if !self.local_bounds().intersects(&transformed_ray) {
+            return None;
+        }
         let mut intersections: Vec<Intersection> = vec![];
+        let untransformed = self.inverse == Matrix4x4::identity();
         for object in &self.objects {
             let object_intersections = object.intersect(&transformed_ray);
-            if object_intersections != None {
-                for intersection in object_intersections.unwrap() {
+            if untransformed {
+                intersections.extend(object_intersections.into_iter().flatten());
+            }
+            else if let Some(object_intersections) = object_intersections {
+                for intersection in object_intersections {
                     let new_intersection;
                     if intersection.u == None {
                         new_intersection = Intersection::new(
                             intersection.t,
                             Ray::position(&transformed_ray, intersection.t),
-                            (intersection.object).normal(&Ray::position(&ray, intersection.t), None, None),
+                            (intersection.object).normal_at(&Ray::position(ray, intersection.t), None, None, ray.time),
                             intersection.object,
                         );
                     }
@@ -75,7 +166,7 @@ impl<'a> Object for Group {
                         new_intersection = Intersection::new(
                             intersection.t,
                             Ray::position(&transformed_ray, intersection.t),
-                            (intersection.object).normal(&Ray::position(&ray, intersection.t), intersection.u, intersection.v),
+                            (intersection.object).normal_at(&Ray::position(ray, intersection.t), intersection.u, intersection.v, ray.time),
                             intersection.object,
                         );
                     }
@@ -88,12 +179,60 @@ impl<'a> Object for Group {
         }
         None
     }
+}
+
+impl Object for Group {
+    //Returns the group material
+    fn get_material(&self) -> &Material {
+        &self.material
+    }
+
+    //Returns the group matrix
+    fn get_inverse(&self) -> &Matrix4x4 {
+        &self.inverse
+    }
+
+    //Intersects a ray with a group
+    //Moving groups move the ray back to where the group starts, then turn the normals they find to match the group at the ray's time
+    fn intersect(&self, ray: &Ray) -> Option<Vec<Intersection<'_>>> {
+        let rewind = match Motion::rewind(&self.motion, &self.transform, ray.time) {
+            Some(rewind) => rewind,
+            None => return self.intersect_still(ray),
+        };
+        let mut intersections = self.intersect_still(&Ray::transform(ray, &rewind))?;
+        let turn = rewind.transpose();
+        for intersection in &mut intersections {
+            let mut normal = &turn * &intersection.normal;
+            normal.3 = 0.0;
+            intersection.normal = normal.normalize();
+        }
+        Some(intersections)
+    }
 
     //The normal of a group is always a vector pointing directly upwards
     fn normal(&self, _world_point: &Vec4, _u: Option<f32>, _v: Option<f32>) -> Vec4 {
         panic!("Cannot find the normal of a group");
     }
 
+    //Finds the bounds of every child transformed into the space of the group's parent
+    fn bounds(&self) -> BoundingBox {
+        Motion::bounds(&self.motion, &self.transform, self.local_bounds())
+    }
+
+    //Recursively splits large groups into subgroups so rays only test children whose bounds they hit
+    fn divide(&mut self, threshold: usize) {
+        if self.objects.len() > threshold {
+            if let Some((left, right)) = self.split_children() {
+                self.objects.push(Box::new(left));
+                self.objects.push(Box::new(right));
+            }
+        }
+        for object in &mut self.objects {
+            object.divide(threshold);
+        }
+        self.reset_bounds();
+    }
+
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
         &self.parent_inverses
     }
@@ -119,7 +258,7 @@ impl<'a> Object for Group {
     fn add_to_group(mut self, group: &mut Group) {
         self.push_parent_inverse(group.get_inverse().clone());
         self.set_parent_material(&group.material);
-        group.objects.push(Box::new(self));
+        group.push(Box::new(self));
     }
 
     fn eq(&self, other: &dyn Object) -> bool {
@@ -3,6 +3,7 @@ pub mod plane;
 pub mod cube;
 pub mod cylinder;
 pub mod cone;
+pub mod disk;
 
 pub mod triangle;
 pub mod smooth_triangle;
@@ -12,4 +13,7 @@ pub mod csg;
 
 pub mod parser;
 
-pub mod object;
\ No newline at end of file
+pub mod bounds;
+pub mod motion;
+
+pub mod object;
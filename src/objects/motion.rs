@@ -0,0 +1,208 @@
+use crate::core::matrix::Matrix4x4;
+use crate::core::vector::Vec4;
+use crate::objects::bounds::BoundingBox;
+
+//Motion stores where a moving object is at the close of the shutter
+//The object moves from its transform at time 0.0 to the end transform at time 1.0
+#[derive(Debug, PartialEq, Clone)]
+pub struct Motion {
+    pub end: Matrix4x4,
+}
+
+//Largest difference from a right angle allowed between the axes of a transform which is split into a pose
+const POSE_EPSILON: f32 = 0.001;
+
+//Largest difference from 1.0 in the alignment of two rotations which are treated as the same
+const TURN_EPSILON: f32 = 0.000001;
+
+//A Pose is a transform split into a scale along each axis, then a rotation (as a unit quaternion), then a translation
+//Blending the parts separately moves objects along real paths, where blending matrices would squash turning objects
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pose {
+    pub translation: (f32, f32, f32),
+    pub rotation: (f32, f32, f32, f32), //Quaternion stored as (w, x, y, z)
+    pub scale: (f32, f32, f32),
+}
+
+impl Pose {
+    //Splits a transform into a pose
+    //Transforms which shear (including scales along axes that are not the object's own) can not be split
+    pub fn from_matrix(matrix: &Matrix4x4) -> Option<Pose> {
+        let column = |j: usize| (matrix.get(0, j), matrix.get(1, j), matrix.get(2, j));
+        let length = |(x, y, z): (f32, f32, f32)| (x * x + y * y + z * z).sqrt();
+        let mut columns = [column(0), column(1), column(2)];
+        let mut scale = [length(columns[0]), length(columns[1]), length(columns[2])];
+        if scale.contains(&0.0) {
+            return None;
+        }
+        for (column, s) in columns.iter_mut().zip(scale.iter()) {
+            *column = (column.0 / s, column.1 / s, column.2 / s);
+        }
+
+        //A mirrored transform is stored as a rotation with a negative scale
+        let (a, b, c) = (columns[0], columns[1], columns[2]);
+        let cross = (b.1 * c.2 - b.2 * c.1, b.2 * c.0 - b.0 * c.2, b.0 * c.1 - b.1 * c.0);
+        if a.0 * cross.0 + a.1 * cross.1 + a.2 * cross.2 < 0.0 {
+            scale[0] = -scale[0];
+            columns[0] = (-a.0, -a.1, -a.2);
+        }
+
+        let dot = |p: (f32, f32, f32), q: (f32, f32, f32)| p.0 * q.0 + p.1 * q.1 + p.2 * q.2;
+        let (a, b, c) = (columns[0], columns[1], columns[2]);
+        if dot(a, b).abs() > POSE_EPSILON || dot(b, c).abs() > POSE_EPSILON || dot(a, c).abs() > POSE_EPSILON {
+            return None;
+        }
+
+        //Finds the quaternion of the rotation, starting from its largest part to stay accurate
+        let m = [[a.0, b.0, c.0], [a.1, b.1, c.1], [a.2, b.2, c.2]];
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let rotation = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            (0.25 * s, (m[2][1] - m[1][2]) / s, (m[0][2] - m[2][0]) / s, (m[1][0] - m[0][1]) / s)
+        }
+        else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+            ((m[2][1] - m[1][2]) / s, 0.25 * s, (m[0][1] + m[1][0]) / s, (m[0][2] + m[2][0]) / s)
+        }
+        else if m[1][1] > m[2][2] {
+            let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+            ((m[0][2] - m[2][0]) / s, (m[0][1] + m[1][0]) / s, 0.25 * s, (m[1][2] + m[2][1]) / s)
+        }
+        else {
+            let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+            ((m[1][0] - m[0][1]) / s, (m[0][2] + m[2][0]) / s, (m[1][2] + m[2][1]) / s, 0.25 * s)
+        };
+
+        Some(Pose {
+            translation: (matrix.get(0, 3), matrix.get(1, 3), matrix.get(2, 3)),
+            rotation,
+            scale: (scale[0], scale[1], scale[2]),
+        })
+    }
+
+    //Builds the transform of the pose
+    pub fn matrix(&self) -> Matrix4x4 {
+        let (w, x, y, z) = self.rotation;
+        let (sx, sy, sz) = self.scale;
+        let (tx, ty, tz) = self.translation;
+        Matrix4x4::new(
+            ((1.0 - 2.0 * (y * y + z * z)) * sx, 2.0 * (x * y - z * w) * sy, 2.0 * (x * z + y * w) * sz, tx),
+            (2.0 * (x * y + z * w) * sx, (1.0 - 2.0 * (x * x + z * z)) * sy, 2.0 * (y * z - x * w) * sz, ty),
+            (2.0 * (x * z - y * w) * sx, 2.0 * (y * z + x * w) * sy, (1.0 - 2.0 * (x * x + y * y)) * sz, tz),
+            (0.0, 0.0, 0.0, 1.0),
+        )
+    }
+
+    //Finds the cosine of half the angle turned between two poses
+    fn alignment(&self, other: &Pose) -> f32 {
+        let (a, b) = (self.rotation, other.rotation);
+        a.0 * b.0 + a.1 * b.1 + a.2 * b.2 + a.3 * b.3
+    }
+
+    //Blends two poses, moving and scaling in straight lines and turning at a steady rate the short way around
+    pub fn blend(&self, other: &Pose, amount: f32) -> Pose {
+        let lerp = |a: f32, b: f32| a + (b - a) * amount;
+        let (a, mut b) = (self.rotation, other.rotation);
+        let mut cos = self.alignment(other);
+        if cos < 0.0 {
+            b = (-b.0, -b.1, -b.2, -b.3);
+            cos = -cos;
+        }
+        //Nearly equal rotations are blended directly to avoid dividing by a tiny sine
+        let (wa, wb) = if cos > 0.9995 {
+            (1.0 - amount, amount)
+        }
+        else {
+            let angle = cos.acos();
+            (((1.0 - amount) * angle).sin() / angle.sin(), (amount * angle).sin() / angle.sin())
+        };
+        let q = (wa * a.0 + wb * b.0, wa * a.1 + wb * b.1, wa * a.2 + wb * b.2, wa * a.3 + wb * b.3);
+        let length = (q.0 * q.0 + q.1 * q.1 + q.2 * q.2 + q.3 * q.3).sqrt();
+        Pose {
+            translation: (lerp(self.translation.0, other.translation.0), lerp(self.translation.1, other.translation.1), lerp(self.translation.2, other.translation.2)),
+            rotation: (q.0 / length, q.1 / length, q.2 / length, q.3 / length),
+            scale: (lerp(self.scale.0, other.scale.0), lerp(self.scale.1, other.scale.1), lerp(self.scale.2, other.scale.2)),
+        }
+    }
+}
+
+impl Motion {
+    //Creates a new Motion
+    pub fn new(end: Matrix4x4) -> Motion {
+        Motion { end }
+    }
+
+    //Finds the transform of an object at a time during the shutter interval
+    //The transforms are split into poses so turning objects keep their shape, while transforms which shear are blended
+    //entry by entry (scene files reject these)
+    pub fn transform_at(&self, start: &Matrix4x4, time: f32) -> Matrix4x4 {
+        match (Pose::from_matrix(start), Pose::from_matrix(&self.end)) {
+            (Some(first), Some(last)) => first.blend(&last, time).matrix(),
+            _ => start.lerp(&self.end, time),
+        }
+    }
+
+    //Finds the matrix which moves a point from where an object is at a time back to where it starts
+    //Objects which are not moving (or rays sent when the shutter opens) need no rewinding, and neither do objects
+    //which flatten at that time (such as ones scaled through zero), so they are seen where they start
+    pub fn rewind(motion: &Option<Motion>, start: &Matrix4x4, time: f32) -> Option<Matrix4x4> {
+        match motion {
+            Some(motion) if time != 0.0 => motion.transform_at(start, time).inverse().map(|inverse| start * inverse),
+            _ => None,
+        }
+    }
+
+    //Checks that an object can be moved back from every time in the shutter interval
+    //Poses only flatten when a scale changes sign, so this is the case when the start and end are both poses
+    //with scales of the same sign
+    pub fn is_invertible(&self, start: &Matrix4x4) -> bool {
+        match (Pose::from_matrix(start), Pose::from_matrix(&self.end)) {
+            (Some(first), Some(last)) => {
+                first.scale.0 * last.scale.0 > 0.0 && first.scale.1 * last.scale.1 > 0.0 && first.scale.2 * last.scale.2 > 0.0
+            }
+            _ => false,
+        }
+    }
+
+    //Finds the inverse transform of an object at a time, if it differs from the object's inverse
+    pub fn inverse_at(motion: &Option<Motion>, start: &Matrix4x4, inverse: &Matrix4x4, time: f32) -> Option<Matrix4x4> {
+        Motion::rewind(motion, start, time).map(|rewind| inverse * rewind)
+    }
+
+    //Finds the box enclosing an object through its whole motion
+    //Objects which move and scale without turning keep every point between its start and end, so both ends are
+    //enough, while turning objects are bounded by the sphere around their box swept along the path
+    pub fn bounds(motion: &Option<Motion>, start: &Matrix4x4, local: &BoundingBox) -> BoundingBox {
+        let mut bounds = local.transform(start);
+        let motion = match motion {
+            Some(motion) => motion,
+            None => return bounds,
+        };
+        bounds.merge(&local.transform(&motion.end));
+        let (first, last) = match (Pose::from_matrix(start), Pose::from_matrix(&motion.end)) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return bounds,
+        };
+        if first.alignment(&last).abs() > 1.0 - TURN_EPSILON || local.is_empty() {
+            return bounds;
+        }
+        if !local.is_finite() {
+            return BoundingBox::infinite();
+        }
+
+        //Points of the object stay within reach of its translation, which moves in a straight line
+        let center = local.centroid();
+        let half = Vec4::magnitude(&Vec4::new(local.max.0 - center.0, local.max.1 - center.1, local.max.2 - center.2, 0.0));
+        let reach = [&first, &last].iter().map(|pose| {
+            let (sx, sy, sz) = pose.scale;
+            let offset = Vec4::magnitude(&Vec4::new(center.0 * sx, center.1 * sy, center.2 * sz, 0.0));
+            offset + half * sx.abs().max(sy.abs()).max(sz.abs())
+        }).fold(0.0, f32::max);
+        let mut swept = BoundingBox::empty();
+        for (x, y, z) in [first.translation, last.translation].iter() {
+            swept.add_point(&Vec4::new(x - reach, y - reach, z - reach, 1.0));
+            swept.add_point(&Vec4::new(x + reach, y + reach, z + reach, 1.0));
+        }
+        swept
+    }
+}
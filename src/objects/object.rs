@@ -4,15 +4,22 @@ use crate::ray_tracing::intersection::Intersection;
 use crate::materials::material::*;
 use crate::ray_tracing::ray::Ray;
 use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
 use std::fmt::Debug;
 use std::any::Any;
 
 //Trait which holds necessary methods for an object
-pub trait Object: Debug + ObjectClone {
+//Objects are shared between render threads so they must be Send + Sync
+pub trait Object: Debug + ObjectClone + Send + Sync {
     //Returns the object material
     fn get_material(&self) -> &Material;
 
     fn get_inverse(&self) -> &Matrix4x4;
+
+    //Returns the object's inverse matrix at a time in the shutter interval
+    fn get_inverse_at(&self, _time: f32) -> Matrix4x4 {
+        self.get_inverse().clone()
+    }
     
     //Intersects a given object with a ray
     fn intersect(&self, ray: &Ray) -> Option<Vec<Intersection>>;
@@ -20,9 +27,22 @@ pub trait Object: Debug + ObjectClone {
     //Finds the normal of an object at a given point
     fn normal(&self, _world_point: &Vec4, u: Option<f32>, v: Option<f32>) -> Vec4;
 
+    //Finds the normal of an object at a given point and time in the shutter interval
+    //Objects which cannot move have the same normal at every time
+    fn normal_at(&self, world_point: &Vec4, u: Option<f32>, v: Option<f32>, _time: f32) -> Vec4 {
+        self.normal(world_point, u, v)
+    }
+
     //Adds a given object to a group
     fn add_to_group(self, group: &mut Group);
 
+    //Finds the box enclosing the object in the space of its parent
+    fn bounds(&self) -> BoundingBox;
+
+    //Splits the children of an object into a bounding volume hierarchy
+    //Objects without children have nothing to split
+    fn divide(&mut self, _threshold: usize) {}
+
     //Modifiers for the object's parent inverse list
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4>;
     fn push_parent_inverse(&mut self, inverse: Matrix4x4);
@@ -31,6 +51,11 @@ pub trait Object: Debug + ObjectClone {
     fn get_parent_material(&self) -> &Option<Material>;
     fn set_parent_material(&mut self, material: &Material);
 
+    //Finds the material the object is shaded with, which is its parent's material when it has one
+    fn get_effective_material(&self) -> &Material {
+        self.get_parent_material().as_ref().unwrap_or_else(|| self.get_material())
+    }
+
     //Methods used to allow PartialEq between objects
     fn eq(&self, other: &dyn Object) -> bool;
     fn as_any(&self) -> &dyn Any;
@@ -1,9 +1,9 @@
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
 use crate::core::vector::Vec4;
 use crate::objects::triangle::Triangle;
 use crate::objects::smooth_triangle::SmoothTriangle;
-use crate::objects::group::Group;
+use crate::objects::group::*;
 use crate::objects::object::*;
 use crate::materials::material::*;
 
@@ -22,142 +22,81 @@ impl Parser {
         for smooth_triangle in self.smooth_triangles {
             smooth_triangle.add_to_group(group);
         }
+        //Meshes contain thousands of triangles so they are always split into a hierarchy
+        group.divide(BVH_THRESHOLD);
     }
 
-    pub fn parse_obj(file: File) -> Parser {
+    //Reads the triangles of an obj file
+    //Malformed lines give an InvalidData error naming the line of the file
+    pub fn parse_obj(file: File) -> io::Result<Parser> {
         let file = BufReader::new(file);
-        let lines: Vec<String> = file.lines().map(|line| line.unwrap()).collect();
         let mut vertices: Vec<Vec4> = vec![];
         let mut triangles: Vec<Triangle> = vec![];
         let mut normals: Vec<Vec4> = vec![];
         let mut smooth_triangles: Vec<SmoothTriangle> = vec![];
 
-        let mut min_x = f32::INFINITY;
-        let mut max_x = -f32::INFINITY;
-
-        let mut min_y = f32::INFINITY;
-        let mut max_y = -f32::INFINITY;
-
-        let mut min_z = f32::INFINITY;
-        let mut max_z = -f32::INFINITY;
-
-        for line in lines {
-            if line.chars().nth(0) == None {
-                continue
-            }
-            if line.chars().nth(0).unwrap() == 'v' {
-                if line.chars().nth(1).unwrap() == 'n' {
-                    let split: Vec<&str> = line.split(" ").collect();
-                    let mut starting_index = 1;
-                    while split[starting_index] == "" {
-                        starting_index += 1;
-                    }
-                    let x = split[starting_index].parse::<f32>().unwrap();
-
-                    let y = split[starting_index + 1].parse::<f32>().unwrap();
-
-                    let z = split[starting_index + 2].parse::<f32>().unwrap();
-
-                    normals.push(Vec4(x, y, z, 0.0));
+        for (number, line) in file.lines().enumerate() {
+            let line = line?;
+            let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", number + 1, message));
+            let split: Vec<&str> = line.split_whitespace().collect();
+            let coordinates = || -> io::Result<(f32, f32, f32)> {
+                if split.len() < 4 {
+                    return Err(invalid("expected three coordinates"));
                 }
-                else {
-                    let split: Vec<&str> = line.split(" ").collect();
-                    let mut starting_index = 1;
-                    while split[starting_index] == "" {
-                        starting_index += 1;
-                    }
-                    let x = split[starting_index].parse::<f32>().unwrap();
-                    if x > max_x {
-                        max_x = x;
-                    }
-                    else if x < min_x {
-                        min_x = x
-                    }
-
-                    let y = split[starting_index + 1].parse::<f32>().unwrap();
-                    if y > max_y {
-                        max_y = y;
-                    }
-                    else if y < min_y {
-                        min_y = y;
-                    }
-
-                    let z = split[starting_index + 2].parse::<f32>().unwrap();
-                    if z > max_z {
-                        max_z = z;
-                    }
-                    else if z < min_z {
-                        min_z = z;
-                    }
-                    
+                let parse = |value: &str| value.parse::<f32>().map_err(|_| invalid(&format!("{} is not a number", value)));
+                Ok((parse(split[1])?, parse(split[2])?, parse(split[3])?))
+            };
+            match split.first() {
+                Some(&"v") => {
+                    let (x, y, z) = coordinates()?;
                     vertices.push(Vec4(x, y, z, 1.0));
                 }
-            }
-            if line.chars().nth(0).unwrap() == 'f' {
-                let mut split: Vec<&str> = line.split(" ").collect();
-                split.retain(|string| string != &"");
-                if split[1].contains("/") {
-                    if split.len() > 4 {
-                        let mut vertex_indices: Vec<usize> = vec![];
-                        let mut normal_indices: Vec<usize> = vec![];
-                        for index in 1..split.len() {
-                            let subsplit: Vec<&str> = split[index].split("/").collect();
-                            let v = subsplit[0].parse::<usize>().unwrap() - 1;
-                            let vn = subsplit[subsplit.len() - 1].parse::<usize>().unwrap() - 1;
-                            vertex_indices.push(v);
-                            normal_indices.push(vn);
-                        }
-                        let polygon_triangles = Parser::fan_triangulation_smooth(&vertices, &normals, vertex_indices, normal_indices); 
-                        for triangle in polygon_triangles {
-                            smooth_triangles.push(triangle);
-                        } 
-                    }
-                    else {
-                        let subsplit1: Vec<&str> = split[1].split("/").collect();
-                        let v1 = subsplit1[0].replace("-", "").parse::<usize>().unwrap() - 1;
-                        let vn1 = subsplit1[subsplit1.len() - 1].replace("-", "").parse::<usize>().unwrap() - 1;
-
-                        let subsplit2: Vec<&str> = split[2].split("/").collect();
-                        let v2 = subsplit2[0].replace("-", "").parse::<usize>().unwrap() - 1;
-                        let vn2 = subsplit2[subsplit2.len() - 1].replace("-", "").parse::<usize>().unwrap() - 1;
-
-                        let subsplit3: Vec<&str> = split[3].split("/").collect();
-                        let v3 = subsplit3[0].replace("-", "").parse::<usize>().unwrap() - 1;
-                        let vn3 = subsplit3[subsplit3.len() - 1].replace("-", "").parse::<usize>().unwrap() - 1;
-
-                        smooth_triangles.push(SmoothTriangle::new(vertices[v1].clone(), vertices[v2].clone(), vertices[v3].clone(), normals[vn1].clone(), normals[vn2].clone(), normals[vn3].clone(), Material::default()));
-                    } 
+                Some(&"vn") => {
+                    let (x, y, z) = coordinates()?;
+                    normals.push(Vec4(x, y, z, 0.0));
                 }
-                else {
-                    if split.len() > 4 {
-                        let indices: Vec<usize> = split[1..].iter().map(|index| index.parse::<usize>().unwrap() - 1).collect();
-                        let polygon_triangles = Parser::fan_triangulation(&vertices, indices);
-                        for triangle in polygon_triangles {
-                            triangles.push(triangle);
+                Some(&"f") => {
+                    if split.len() < 4 {
+                        return Err(invalid("a face needs at least three vertices"));
+                    }
+                    //Indices count from 1, or back from the end of the list when negative
+                    let index = |value: &str, count: usize| -> io::Result<usize> {
+                        let index = value.parse::<i64>().map_err(|_| invalid(&format!("{} is not an index", value)))?;
+                        let position = if index < 0 { count as i64 + index } else { index - 1 };
+                        if position < 0 || position >= count as i64 {
+                            return Err(invalid(&format!("index {} is out of range", value)));
+                        }
+                        Ok(position as usize)
+                    };
+                    let mut vertex_indices: Vec<usize> = vec![];
+                    let mut normal_indices: Vec<usize> = vec![];
+                    for corner in &split[1..] {
+                        //Corners are written as v, v/vt, v//vn or v/vt/vn
+                        let parts: Vec<&str> = corner.split('/').collect();
+                        vertex_indices.push(index(parts[0], vertices.len())?);
+                        if let Some(normal) = parts.get(2).filter(|normal| !normal.is_empty()) {
+                            normal_indices.push(index(normal, normals.len())?);
                         }
                     }
+                    if normal_indices.is_empty() {
+                        triangles.append(&mut Parser::fan_triangulation(&vertices, vertex_indices));
+                    }
+                    else if normal_indices.len() == vertex_indices.len() {
+                        smooth_triangles.append(&mut Parser::fan_triangulation_smooth(&vertices, &normals, vertex_indices, normal_indices));
+                    }
                     else {
-                        let v1 = split[1].parse::<usize>().unwrap() - 1;
-                        let v2 = split[2].parse::<usize>().unwrap() - 1;
-                        let v3 = split[3].parse::<usize>().unwrap() - 1;
-                        triangles.push(Triangle::new(vertices[v1].clone(), vertices[v2].clone(), vertices[v3].clone(), Material::default()));
+                        return Err(invalid("either every corner of a face has a normal or none do"));
                     }
                 }
-            } 
-            else {
-                continue
+                _ => continue,
             }
         }
-        println!("Dimensions: ");
-        println!("min x: {}, max x: {}", min_x, max_x);
-        println!("min y: {}, max y: {}", min_y, max_y);
-        println!("min z: {}, max z: {}", min_z, max_z);
-        Parser {
+        Ok(Parser {
             vertices,
             normals,
             triangles,
             smooth_triangles,
-        }
+        })
     }
 
     fn fan_triangulation(vertices: &Vec<Vec4>, indices: Vec<usize>) -> Vec<Triangle> {
@@ -4,6 +4,7 @@ use crate::objects::object::*;
 use crate::materials::material::*;
 use crate::ray_tracing::ray::Ray;
 use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
 use crate::ray_tracing::intersection::Intersection;
 use crate::misc::utils::*;
 use std::any::Any;
@@ -72,6 +73,11 @@ impl Object for Plane {
         normal_to_world(&self.parent_inverses, &result.normalize())
     }
 
+    //A plane extends infinitely along x and z
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Vec4(-f32::INFINITY, 0.0, -f32::INFINITY, 1.0), Vec4(f32::INFINITY, 0.0, f32::INFINITY, 1.0)).transform(&self.transform)
+    }
+
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
         &self.parent_inverses
     }
@@ -91,7 +97,7 @@ impl Object for Plane {
     fn add_to_group(mut self, group: &mut Group) {
         self.push_parent_inverse(group.get_inverse().clone());
         self.set_parent_material(&group.material);
-        group.objects.push(Box::new(self));
+        group.push(Box::new(self));
     }
 
     fn eq(&self, other: &dyn Object) -> bool {
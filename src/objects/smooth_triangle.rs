@@ -5,6 +5,7 @@ use crate::objects::object::*;
 use crate::ray_tracing::intersection::Intersection;
 use crate::materials::material::*;
 use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
 use crate::ray_tracing::ray::Ray;
 use std::any::Any;
 
@@ -107,6 +108,15 @@ impl Object for SmoothTriangle {
         normal_to_world(&self.parent_inverses, &(&self.n2 * u.unwrap() + &self.n3 * v.unwrap() + &self.n1 * (1.0 - u.unwrap() - v.unwrap())).normalize())
     }
 
+    //Finds the bounds of a smooth triangle from its vertices
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        bounds.add_point(&self.p1);
+        bounds.add_point(&self.p2);
+        bounds.add_point(&self.p3);
+        bounds
+    }
+
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
         &self.parent_inverses
     }
@@ -126,7 +136,7 @@ impl Object for SmoothTriangle {
     fn add_to_group(mut self, group: &mut Group) {
         self.push_parent_inverse(group.get_inverse().clone());
         self.set_parent_material(&group.material);
-        group.objects.push(Box::new(self));
+        group.push(Box::new(self));
     }
 
     fn eq(&self, other: &dyn Object) -> bool {
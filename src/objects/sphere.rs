@@ -4,6 +4,8 @@ use crate::objects::object::*;
 use crate::ray_tracing::intersection::Intersection;
 use crate::materials::material::*;
 use crate::objects::group::Group;
+use crate::objects::bounds::BoundingBox;
+use crate::objects::motion::Motion;
 use crate::ray_tracing::ray::Ray;
 use std::any::Any;
 
@@ -15,6 +17,7 @@ pub struct Sphere {
     pub material: Material,
     pub parent_inverses: Vec<Matrix4x4>,
     pub parent_material: Option<Material>,
+    pub motion: Option<Motion>,
 }
 
 impl Sphere {
@@ -26,6 +29,7 @@ impl Sphere {
             material: Material::default(),
             parent_inverses: vec![],
             parent_material: None,
+            motion: None,
         }
     }
 
@@ -40,6 +44,7 @@ impl Sphere {
             material: material,
             parent_inverses: vec![],
             parent_material: None,
+            motion: None,
         }
     }
 
@@ -50,8 +55,22 @@ impl Sphere {
             material,
             parent_inverses: vec![],
             parent_material: None,
+            motion: None,
         }
     }
+
+    //Finds the normal of a given point on a sphere with a given inverse transform
+    fn normal_with_inverse(&self, world_point: &Vec4, inverse: &Matrix4x4) -> Vec4 {
+        //Applies inverse transformations to the point
+        let group_point = world_to_object(&self.parent_inverses, world_point);
+        let object_point = inverse * group_point;
+        let object_normal = object_point - Vec4::new(0.0, 0.0, 0.0, 1.0);
+        //Computes the world normal
+        let mut world_normal = &inverse.transpose() * object_normal;
+        world_normal.3 = 0.0;
+        let world_normal = world_normal.normalize();
+        normal_to_world(&self.parent_inverses, &world_normal)
+    }
 }
 
 impl Object for Sphere {
@@ -65,9 +84,14 @@ impl Object for Sphere {
         &self.inverse
     }
 
+    fn get_inverse_at(&self, time: f32) -> Matrix4x4 {
+        Motion::inverse_at(&self.motion, &self.transform, &self.inverse, time).unwrap_or_else(|| self.inverse.clone())
+    }
+
     //Intersects a ray with a sphere
     fn intersect(&self, ray: &Ray) -> Option<Vec<Intersection>> {
-        let transformed_ray = Ray::transform(ray, &self.inverse);
+        let moved_inverse = Motion::inverse_at(&self.motion, &self.transform, &self.inverse, ray.time);
+        let transformed_ray = Ray::transform(ray, moved_inverse.as_ref().unwrap_or(&self.inverse));
         let vector_to_unit_sphere = &transformed_ray.origin - Vec4::new(0.0, 0.0, 0.0, 1.0);
         let a = Vec4::dot(
             &transformed_ray.direction,
@@ -83,14 +107,14 @@ impl Object for Sphere {
             let i1 = Intersection::new(
                 t1,
                 Ray::position(&ray, t1),
-                self.normal(&Ray::position(&ray, t1), None, None),
+                self.normal_at(&Ray::position(ray, t1), None, None, ray.time),
                 self,
             );
             let t2 = (-b + discriminant.sqrt()) / (2.0 * a);
             let i2 = Intersection::new(
                 t2,
                 Ray::position(&ray, t2),
-                self.normal(&Ray::position(&ray, t2), None, None),
+                self.normal_at(&Ray::position(ray, t2), None, None, ray.time),
                 self,
             );
             Some(vec![i1, i2])
@@ -101,15 +125,19 @@ impl Object for Sphere {
 
     //Finds the normal of a given point on a sphere
     fn normal(&self, world_point: &Vec4, _u: Option<f32>, _v: Option<f32>) -> Vec4 {
-        //Applies inverse transformations to the point
-        let group_point = world_to_object(&self.parent_inverses, world_point);
-        let object_point = &self.inverse * group_point;
-        let object_normal = object_point - Vec4::new(0.0, 0.0, 0.0, 1.0);
-        //Computes the world normal
-        let mut world_normal = &self.inverse.transpose() * object_normal;
-        world_normal.3 = 0.0;
-        let world_normal = world_normal.normalize();
-        normal_to_world(&self.parent_inverses, &world_normal)
+        self.normal_with_inverse(world_point, &self.inverse)
+    }
+
+    //Finds the normal of a given point on a sphere where it is at a time
+    fn normal_at(&self, world_point: &Vec4, _u: Option<f32>, _v: Option<f32>, time: f32) -> Vec4 {
+        let moved_inverse = Motion::inverse_at(&self.motion, &self.transform, &self.inverse, time);
+        self.normal_with_inverse(world_point, moved_inverse.as_ref().unwrap_or(&self.inverse))
+    }
+
+    //Finds the bounds of a sphere which fits inside the unit cube before transformation
+    fn bounds(&self) -> BoundingBox {
+        let local = BoundingBox::new(Vec4(-1.0, -1.0, -1.0, 1.0), Vec4(1.0, 1.0, 1.0, 1.0));
+        Motion::bounds(&self.motion, &self.transform, &local)
     }
 
     fn get_parent_inverses(&self) -> &Vec<Matrix4x4> {
@@ -131,7 +159,7 @@ impl Object for Sphere {
     fn add_to_group(mut self, group: &mut Group) {
         self.push_parent_inverse(group.get_inverse().clone());
         self.set_parent_material(&group.material);
-        group.objects.push(Box::new(self));
+        group.push(Box::new(self));
     }
 
     fn eq(&self, other: &dyn Object) -> bool {
@@ -8,6 +8,7 @@ use crate::world::scene::*;
 pub struct Ray {
     pub origin: Vec4,
     pub direction: Vec4,
+    pub time: f32, //Point in the shutter interval the ray is sent at, from 0.0 (open) to 1.0 (close)
 }
 
 impl Ray where {
@@ -16,6 +17,7 @@ impl Ray where {
         Ray {
             origin: Vec4::new(origin.0, origin.1, origin.2, 1.0),
             direction: Vec4::new(direction.0, direction.1, direction.2, 0.0),
+            time: 0.0,
         }
     }
 
@@ -24,9 +26,16 @@ impl Ray where {
         Ray {
             origin,
             direction,
+            time: 0.0,
         }
     }
 
+    //Sends the ray at a different time
+    pub fn at_time(mut self, time: f32) -> Ray {
+        self.time = time;
+        self
+    }
+
     //Calculates the position of a ray
     pub fn position(ray: &Ray, t: f32) -> Vec4 {
         &ray.origin + (&ray.direction * t)
@@ -37,6 +46,7 @@ impl Ray where {
         Ray {
             origin: matrix * &ray.origin,
             direction: matrix * &ray.direction,
+            time: ray.time,
         }
     }
 
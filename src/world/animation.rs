@@ -0,0 +1,92 @@
+//Ways a value changes between one keyframe and the next
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Interpolation {
+    //Changes at a constant rate
+    Linear,
+    //Starts and stops slowly, following a smoothstep curve
+    Ease,
+    //Follows a cubic Bezier timing curve from (0, 0) to (1, 1) with control points (x1, y1) and (x2, y2)
+    //The x coordinates must be between 0 and 1 so the curve never goes back in time
+    Bezier(f32, f32, f32, f32),
+}
+
+//Number of halvings used to find the point on a Bezier curve at a given time
+const BEZIER_STEPS: usize = 24;
+
+impl Interpolation {
+    //Finds how far a value has moved towards the next keyframe, given how far through the gap between them the frame is
+    pub fn amount(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Interpolation::Linear => t,
+            Interpolation::Ease => t * t * (3.0 - 2.0 * t),
+            Interpolation::Bezier(x1, y1, x2, y2) => {
+                //Finds the curve parameter with an x coordinate of t, then reads the y coordinate there
+                let (mut low, mut high) = (0.0, 1.0);
+                for _ in 0..BEZIER_STEPS {
+                    let middle = (low + high) / 2.0;
+                    if bezier(*x1, *x2, middle) < t {
+                        low = middle;
+                    }
+                    else {
+                        high = middle;
+                    }
+                }
+                bezier(*y1, *y2, (low + high) / 2.0)
+            }
+        }
+    }
+}
+
+//Finds one coordinate of a cubic Bezier curve which starts at 0 and ends at 1
+fn bezier(p1: f32, p2: f32, s: f32) -> f32 {
+    let inverse = 1.0 - s;
+    3.0 * inverse * inverse * s * p1 + 3.0 * inverse * s * s * p2 + s * s * s
+}
+
+//A Keyframe sets a value at a frame
+//The interpolation is used on the way from this keyframe to the next one
+#[derive(Debug, PartialEq, Clone)]
+pub struct Keyframe {
+    pub frame: f32,
+    pub value: Vec<f32>, //A single number, or the components of a point, vector or color
+    pub interpolation: Interpolation,
+}
+
+impl Keyframe {
+    //Creates a new Keyframe
+    pub fn new(frame: f32, value: Vec<f32>, interpolation: Interpolation) -> Keyframe {
+        Keyframe {
+            frame,
+            value,
+            interpolation,
+        }
+    }
+}
+
+//A Track is a value which changes over a list of keyframes
+//Before the first keyframe and after the last one the value holds still
+#[derive(Debug, PartialEq, Clone)]
+pub struct Track {
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl Track {
+    //Creates a new Track, putting the keyframes in order
+    pub fn new(mut keyframes: Vec<Keyframe>) -> Track {
+        keyframes.sort_by(|a, b| a.frame.partial_cmp(&b.frame).unwrap());
+        Track { keyframes }
+    }
+
+    //Finds the value of the track at a frame
+    pub fn value_at(&self, frame: f32) -> Vec<f32> {
+        let next = match self.keyframes.iter().position(|keyframe| keyframe.frame > frame) {
+            Some(0) => return self.keyframes[0].value.clone(),
+            Some(next) => next,
+            None => return self.keyframes.last().map_or(vec![], |keyframe| keyframe.value.clone()),
+        };
+        let (from, to) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let amount = from.interpolation.amount((frame - from.frame) / (to.frame - from.frame));
+        from.value.iter().zip(&to.value).map(|(a, b)| a + (b - a) * amount).collect()
+    }
+}
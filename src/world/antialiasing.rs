@@ -0,0 +1,107 @@
+use rand::Rng;
+
+//Ways sample positions are picked inside a pixel's filter
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Sampler {
+    //An evenly spaced N by N grid, with N rounded up from the square root of the sample count
+    Grid,
+    //An N by N grid with each sample moved to a random spot in its cell
+    Jittered,
+    //Samples placed anywhere at random
+    Random,
+}
+
+impl Sampler {
+    //Picks positions in the unit square for a pixel's samples
+    pub fn offsets<R: Rng>(&self, samples: usize, rng: &mut R) -> Vec<(f32, f32)> {
+        let samples = samples.max(1);
+        let side = (samples as f32).sqrt().ceil() as usize;
+        let mut offsets = vec![];
+        match self {
+            Sampler::Grid | Sampler::Jittered => {
+                for v in 0..side {
+                    for u in 0..side {
+                        let (du, dv) = if *self == Sampler::Jittered { (rng.gen(), rng.gen()) } else { (0.5, 0.5) };
+                        offsets.push(((u as f32 + du) / side as f32, (v as f32 + dv) / side as f32));
+                    }
+                }
+            }
+            Sampler::Random => {
+                for _ in 0..samples {
+                    offsets.push((rng.gen(), rng.gen()));
+                }
+            }
+        }
+        offsets
+    }
+}
+
+//Ways the samples of a pixel are weighted when they are averaged together
+//Filters wider than a pixel send some rays into neighbouring pixels, trading sharpness for smoother edges
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Filter {
+    //Every sample inside the pixel counts equally
+    Box,
+    //Samples are weighted less the further they are from the pixel center, reaching zero one pixel away
+    Tent,
+    //Smooth bell curve falloff reaching zero 1.5 pixels away
+    Gaussian,
+    //Mitchell-Netravali cubic reaching zero 2 pixels away, which keeps edges sharp with slightly negative lobes
+    Mitchell,
+}
+
+//Falloff of the Gaussian filter
+const GAUSSIAN_ALPHA: f32 = 2.0;
+
+impl Filter {
+    //Finds the distance from the pixel center at which samples stop counting
+    pub fn radius(&self) -> f32 {
+        match self {
+            Filter::Box => 0.5,
+            Filter::Tent => 1.0,
+            Filter::Gaussian => 1.5,
+            Filter::Mitchell => 2.0,
+        }
+    }
+
+    //Finds the weight of a sample at an offset from the pixel center, measured in pixels
+    pub fn weight(&self, x: f32, y: f32) -> f32 {
+        self.weight_1d(x) * self.weight_1d(y)
+    }
+
+    //Finds the weight along a single axis
+    fn weight_1d(&self, x: f32) -> f32 {
+        let x = x.abs();
+        let radius = self.radius();
+        if x > radius {
+            return 0.0;
+        }
+        match self {
+            Filter::Box => 1.0,
+            Filter::Tent => radius - x,
+            Filter::Gaussian => ((-GAUSSIAN_ALPHA * x * x).exp() - (-GAUSSIAN_ALPHA * radius * radius).exp()).max(0.0),
+            Filter::Mitchell => {
+                let (b, c) = (1.0 / 3.0, 1.0 / 3.0);
+                if x < 1.0 {
+                    ((12.0 - 9.0 * b - 6.0 * c) * x.powi(3) + (-18.0 + 12.0 * b + 6.0 * c) * x * x + (6.0 - 2.0 * b)) / 6.0
+                }
+                else {
+                    ((-b - 6.0 * c) * x.powi(3) + (6.0 * b + 30.0 * c) * x * x + (-12.0 * b - 48.0 * c) * x + (8.0 * b + 24.0 * c)) / 6.0
+                }
+            }
+        }
+    }
+
+    //Spreads positions in the unit square over the filter, giving each its position in the pixel and its weight
+    pub fn place(&self, offsets: &[(f32, f32)]) -> Vec<(f32, f32, f32)> {
+        let radius = self.radius();
+        offsets
+            .iter()
+            .map(|(u, v)| {
+                let x = (u * 2.0 - 1.0) * radius;
+                let y = (v * 2.0 - 1.0) * radius;
+                (0.5 + x, 0.5 + y, self.weight(x, y))
+            })
+            .collect()
+    }
+}
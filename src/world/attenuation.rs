@@ -0,0 +1,35 @@
+//How the light from a light source weakens as it travels
+//Lights do not weaken with distance by default
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Attenuation {
+    //Light keeps the same brightness at every distance
+    #[default]
+    None,
+    //Brightness is divided by the distance
+    Linear,
+    //Brightness is divided by the distance squared, like real lights
+    InverseSquare,
+    //Brightness is divided by constant + linear * distance + quadratic * distance squared
+    Coefficients { constant: f32, linear: f32, quadratic: f32 },
+}
+
+//Smallest distance used, so points right next to a light do not become infinitely bright
+const MIN_DISTANCE: f32 = 0.0001;
+
+impl Attenuation {
+    //Finds the fraction of a light's intensity which reaches a given distance
+    pub fn factor(&self, distance: f32) -> f32 {
+        let distance = distance.max(MIN_DISTANCE);
+        let divisor = match self {
+            Attenuation::None => 1.0,
+            Attenuation::Linear => distance,
+            Attenuation::InverseSquare => distance * distance,
+            Attenuation::Coefficients { constant, linear, quadratic } => constant + linear * distance + quadratic * distance * distance,
+        };
+        if divisor > 0.0 {
+            1.0 / divisor
+        } else {
+            1.0
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::core::canvas::Canvas;
+use crate::core::color::Color;
+use crate::core::vector::Vec4;
+use crate::materials::patterns::*;
+use std::sync::Arc;
+
+//What a ray sees when it misses every object in the scene
+#[derive(Debug, PartialEq, Clone)]
+pub enum Background {
+    //A single color in every direction
+    Color(Color),
+    //Blends from the bottom color straight down to the top color straight up
+    Gradient { top: Color, bottom: Color },
+    //An equirectangular (latitude and longitude) image wrapped around the scene
+    Environment(Arc<Canvas>),
+}
+
+impl Background {
+    //Finds the color seen looking in a direction
+    pub fn color_at(&self, direction: &Vec4) -> Color {
+        match self {
+            Background::Color(color) => color.clone(),
+            Background::Gradient { top, bottom } => {
+                let t = (direction.normalize().1 + 1.0) / 2.0;
+                bottom * (1.0 - t) + top * t
+            }
+            Background::Environment(canvas) => {
+                let (u, v) = spherical_map(&direction.normalize());
+                sample_canvas(canvas, TextureFilter::Bilinear, u, v, (0, 0, canvas.width, canvas.height))
+            }
+        }
+    }
+}
+
+impl Default for Background {
+    //Scenes are black where nothing is hit by default
+    fn default() -> Background {
+        Background::Color(Color::new(0.0, 0.0, 0.0))
+    }
+}
@@ -1,11 +1,83 @@
 use crate::core::canvas::Canvas;
-use crate::core::color::Color;
+use crate::core::color::*;
 use crate::core::matrix::Matrix4x4;
 use crate::core::vector::Vec4;
+use crate::misc::random;
+use crate::misc::random::LocalRng;
+use crate::objects::motion::Motion;
 use crate::ray_tracing::ray::Ray;
 use crate::world::scene::Scene;
+use crate::world::path_tracing::path_trace;
+use crate::world::settings::*;
+use rand::Rng;
+use std::f32::consts::PI;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+//Width and height of the square tiles the canvas is split into when rendering
+pub const TILE_SIZE: i32 = 16;
+
+//A rectangular region of the canvas rendered by a single thread
+struct Tile {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+//Shapes of the camera lens opening, which give out of focus highlights their shape
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ApertureShape {
+    Disk,
+    //A regular polygon with the given number of sides and a corner pointing up
+    Polygon(usize),
+}
+
+impl ApertureShape {
+    //Picks a random point inside the aperture, which fits in a circle of radius 1
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> (f32, f32) {
+        match self {
+            ApertureShape::Disk => {
+                let radius = rng.gen::<f32>().sqrt();
+                let angle = rng.gen::<f32>() * 2.0 * PI;
+                (radius * angle.cos(), radius * angle.sin())
+            }
+            ApertureShape::Polygon(sides) => {
+                //Picks one of the triangles between the center and each side, then a point inside it
+                let sides = (*sides).max(3);
+                let side = rng.gen_range(0, sides) as f32;
+                let first = PI / 2.0 + side * 2.0 * PI / sides as f32;
+                let second = first + 2.0 * PI / sides as f32;
+                let (mut a, mut b): (f32, f32) = (rng.gen(), rng.gen());
+                if a + b > 1.0 {
+                    a = 1.0 - a;
+                    b = 1.0 - b;
+                }
+                (a * first.cos() + b * second.cos(), a * first.sin() + b * second.sin())
+            }
+        }
+    }
+}
+
+//Ways the camera maps pixels to rays
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Projection {
+    //Rays fan out from a single point through the field of view
+    Perspective,
+    //Parallel rays start across a view of fixed width and height, so objects keep their size at any distance
+    Orthographic,
+    //Every direction around the camera, with longitude across the canvas and latitude down it
+    Equirectangular,
+    //A circle in the middle of the canvas covering the given angle in degrees, with distance from the center
+    //matching the angle from the view direction
+    Fisheye(f32),
+}
 
 //The camera stores all the info relevant to how the scene is viewed
+#[derive(Debug, Clone)]
 pub struct Camera {
     pub hsize: i32,
     pub vsize: i32,
@@ -13,6 +85,15 @@ pub struct Camera {
     pub half_width: f32,
     pub half_height: f32,
     pub transform: Matrix4x4,
+    pub projection: Projection,
+    pub shift_x: f32, //Moves the view left across the image plane, measured one unit in front of the camera
+    pub shift_y: f32, //Moves the view up across the image plane, measured one unit in front of the camera
+    pub aperture: f32, //Radius of the lens, where 0.0 gives a pinhole camera with everything in focus
+    pub focal_distance: f32, //Distance in front of the camera which is in focus
+    pub aperture_shape: ApertureShape,
+    pub shutter_open: f32, //Start of the times rays are sent at, from 0.0 to 1.0
+    pub shutter_close: f32, //End of the times rays are sent at, where a shutter closing when it opens gives no motion blur
+    pub motion: Option<Motion>, //Where the camera's transform ends up when the shutter interval closes
 }
 
 impl Camera {
@@ -38,9 +119,87 @@ impl Camera {
             half_height: _half_height,
             pixel_size,
             transform: Matrix4x4::identity(),
+            projection: Projection::Perspective,
+            shift_x: 0.0,
+            shift_y: 0.0,
+            aperture: 0.0,
+            focal_distance: 1.0,
+            aperture_shape: ApertureShape::Disk,
+            shutter_open: 0.0,
+            shutter_close: 0.0,
+            motion: None,
         }
     }
 
+    //Creates a new orthographic Camera which sees an area of the given width and height in scene units
+    pub fn orthographic(hsize: usize, vsize: usize, view_width: f32, view_height: f32) -> Camera {
+        let mut camera = Camera::new(hsize, vsize, 90.0);
+        camera.projection = Projection::Orthographic;
+        camera.half_width = view_width / 2.0;
+        camera.half_height = view_height / 2.0;
+        camera.pixel_size = view_width / hsize as f32;
+        camera
+    }
+
+    //Creates a new Camera which sees in every direction, giving an equirectangular image twice as wide as it is tall
+    pub fn equirectangular(hsize: usize, vsize: usize) -> Camera {
+        let mut camera = Camera::new(hsize, vsize, 90.0);
+        camera.projection = Projection::Equirectangular;
+        camera
+    }
+
+    //Creates a new fisheye Camera covering an angle in degrees, which may be above 180
+    pub fn fisheye(hsize: usize, vsize: usize, angle_degrees: f32) -> Camera {
+        let mut camera = Camera::new(hsize, vsize, 90.0);
+        camera.projection = Projection::Fisheye(angle_degrees);
+        camera
+    }
+
+    //Changes the size and field of view of the camera while keeping its transform
+    //Orthographic cameras keep the area they see and ignore the field of view, as do equirectangular cameras
+    pub fn resize(&mut self, hsize: usize, vsize: usize, fov_degrees: f32) {
+        let resized = match self.projection {
+            Projection::Perspective => Camera::new(hsize, vsize, fov_degrees),
+            Projection::Orthographic => Camera::orthographic(hsize, vsize, self.half_width * 2.0, self.half_height * 2.0),
+            Projection::Equirectangular => Camera::equirectangular(hsize, vsize),
+            Projection::Fisheye(_) => Camera::fisheye(hsize, vsize, fov_degrees),
+        };
+        self.projection = resized.projection;
+        self.hsize = resized.hsize;
+        self.vsize = resized.vsize;
+        self.pixel_size = resized.pixel_size;
+        self.half_width = resized.half_width;
+        self.half_height = resized.half_height;
+    }
+
+    //Finds the field of view of the camera in degrees
+    pub fn field_of_view(&self) -> f32 {
+        match self.projection {
+            Projection::Equirectangular => 360.0,
+            Projection::Fisheye(angle) => angle,
+            _ => (self.half_width.max(self.half_height).atan() * 2.0).to_degrees(),
+        }
+    }
+
+    //Checks if a point in a pixel is covered by the camera's view, which is only false outside the circle of a fisheye camera
+    pub fn in_view(camera: &Camera, pixel_x: i32, pixel_y: i32, offset_x: f32, offset_y: f32) -> bool {
+        match camera.projection {
+            Projection::Fisheye(_) => {
+                let (x, y) = Camera::fisheye_position(camera, pixel_x, pixel_y, offset_x, offset_y);
+                x.hypot(y) <= 1.0
+            }
+            _ => true,
+        }
+    }
+
+    //Finds where a point in a pixel is on a fisheye image, where the edge of its circle is 1 away from the center
+    fn fisheye_position(camera: &Camera, pixel_x: i32, pixel_y: i32, offset_x: f32, offset_y: f32) -> (f32, f32) {
+        let radius = camera.hsize.min(camera.vsize) as f32 / 2.0;
+        let x = (pixel_x as f32 + offset_x - camera.hsize as f32 / 2.0) / radius;
+        let y = (pixel_y as f32 + offset_y - camera.vsize as f32 / 2.0) / radius;
+        (x, y)
+    }
+
     //Transforms the camera
     pub fn transform(&mut self, matrix: Matrix4x4) {
         self.transform = &self.transform * matrix;
@@ -48,26 +207,13 @@ impl Camera {
 
     //Creates a ray with a vector towards a pixel on the canvas
     pub fn ray_towards_pixel(camera: &Camera, pixel_x: i32, pixel_y: i32) -> Ray {
-        //Offset from canvas edge to the center of the pixel
-        let x_offset = (pixel_x as f32 + 0.5) * camera.pixel_size;
-        let y_offset = (pixel_y as f32 + 0.5) * camera.pixel_size;
-
-        //Undoes transform applied to coordinates due to camera facing towards -z
-        let scene_x = camera.half_width - x_offset;
-        let scene_y = camera.half_height - y_offset;
-
-        //Finds the target pixel and origin coordinates by applying the inverse camera transformations
-        let target_pixel =
-            camera.transform.inverse().unwrap() * Vec4::new(scene_x, scene_y, -1.0, 1.0);
-        let origin = camera.transform.inverse().unwrap() * Vec4::new(0.0, 0.0, 0.0, 1.0);
-
-        //Normalizes the vector
-        let direction = (target_pixel - &origin).normalize();
-
-        Ray::new_from_vec(origin, direction)
+        Camera::ray_towards_pixel_raw(camera, pixel_x, pixel_y, 0.5, 0.5)
     }
 
     //Creates a ray with a vector towards a pixel on the canvas
+    //Cameras with an aperture start the ray at a random point on the lens, aimed so it stays on course at the focal distance
+    //Orthographic rays start on the pixel itself and all point straight ahead
+    //Panoramic rays all start at the camera and turn the pixel into a direction, ignoring the aperture
     pub fn ray_towards_pixel_raw(
         camera: &Camera,
         pixel_x: i32,
@@ -75,111 +221,334 @@ impl Camera {
         offset_x: f32,
         offset_y: f32,
     ) -> Ray {
+        //Picks when in the shutter interval the ray is sent and where the camera is at that time
+        let time = if camera.shutter_close > camera.shutter_open {
+            camera.shutter_open + LocalRng.gen::<f32>() * (camera.shutter_close - camera.shutter_open)
+        }
+        else {
+            camera.shutter_open
+        };
+        //A camera which flattens part way through its motion is left where it starts
+        let inverse = match camera.motion.as_ref().and_then(|motion| motion.transform_at(&camera.transform, time).inverse()) {
+            Some(inverse) => inverse,
+            None => camera.transform.inverse().unwrap(),
+        };
+
+        if let Some(direction) = Camera::panoramic_direction(camera, pixel_x, pixel_y, offset_x, offset_y) {
+            let origin = &inverse * Vec4::new(0.0, 0.0, 0.0, 1.0);
+            return Ray::new_from_vec(origin, (&inverse * direction).normalize()).at_time(time);
+        }
+
         //Offset from canvas edge to the center of the pixel
+        let pixel_height = match camera.projection {
+            Projection::Orthographic => camera.half_height * 2.0 / camera.vsize as f32,
+            _ => camera.pixel_size,
+        };
         let x_offset = (pixel_x as f32 + offset_x) * camera.pixel_size;
-        let y_offset = (pixel_y as f32 + offset_y) * camera.pixel_size;
+        let y_offset = (pixel_y as f32 + offset_y) * pixel_height;
 
         //Undoes transform applied to coordinates due to camera facing towards -z
-        let scene_x = camera.half_width - x_offset;
-        let scene_y = camera.half_height - y_offset;
+        let scene_x = camera.half_width - x_offset + camera.shift_x;
+        let scene_y = camera.half_height - y_offset + camera.shift_y;
+
+        //Finds the point on the lens the ray starts from and the point in focus it passes through
+        let (lens_x, lens_y) = if camera.aperture > 0.0 {
+            let (x, y) = camera.aperture_shape.sample(&mut LocalRng);
+            (x * camera.aperture, y * camera.aperture)
+        }
+        else {
+            (0.0, 0.0)
+        };
+        let distance = if camera.aperture > 0.0 { camera.focal_distance } else { 1.0 };
+        let (start, target) = match camera.projection {
+            Projection::Perspective => (Vec4::new(lens_x, lens_y, 0.0, 1.0), Vec4::new(scene_x * distance, scene_y * distance, -distance, 1.0)),
+            _ => (Vec4::new(scene_x + lens_x, scene_y + lens_y, 0.0, 1.0), Vec4::new(scene_x, scene_y, -distance, 1.0)),
+        };
 
         //Finds the target pixel and origin coordinates by applying the inverse camera transformations
-        let target_pixel =
-            camera.transform.inverse().unwrap() * Vec4::new(scene_x, scene_y, -1.0, 1.0);
-        let origin = camera.transform.inverse().unwrap() * Vec4::new(0.0, 0.0, 0.0, 1.0);
+        let target_pixel = &inverse * target;
+        let origin = &inverse * start;
 
         //Normalizes the vector
         let direction = (target_pixel - &origin).normalize();
 
-        Ray::new_from_vec(origin, direction)
+        Ray::new_from_vec(origin, direction).at_time(time)
     }
 
-    //Renders a scene
-    pub fn render(camera: &Camera, scene: &Scene, canvas: &mut Canvas) {
-        let mut counter = 0;
-        let mut percent = 0;
-        let pixels = &camera.hsize * &camera.vsize;
-        let percentage_update = pixels as f32 / 10.0;
-        for y in 0..camera.vsize {
-            for x in 0..camera.hsize {
-                let ray = Camera::ray_towards_pixel(camera, x, y);
-                let color = Scene::compute_color(ray, scene, 5);
-                if color != None {
-                    canvas.set(color.unwrap().clone(), x, y);
-                }
-                if counter as f32 > percentage_update {
-                    percent += 10;
-                    println!("Render is {}% complete", percent);
-                    counter = 0;
-                } else {
-                    counter += 1;
-                }
+    //Finds the direction of a ray through a point in a pixel before the camera is transformed, for panoramic cameras
+    //Right and down on the canvas are -x and -y, matching the other projections
+    fn panoramic_direction(camera: &Camera, pixel_x: i32, pixel_y: i32, offset_x: f32, offset_y: f32) -> Option<Vec4> {
+        match camera.projection {
+            Projection::Equirectangular => {
+                let longitude = ((pixel_x as f32 + offset_x) / camera.hsize as f32 - 0.5) * 2.0 * PI;
+                let latitude = (0.5 - (pixel_y as f32 + offset_y) / camera.vsize as f32) * PI;
+                Some(Vec4::new(
+                    -longitude.sin() * latitude.cos(),
+                    latitude.sin(),
+                    -longitude.cos() * latitude.cos(),
+                    0.0,
+                ))
+            }
+            Projection::Fisheye(angle) => {
+                let (x, y) = Camera::fisheye_position(camera, pixel_x, pixel_y, offset_x, offset_y);
+                let theta = x.hypot(y) * angle.to_radians() / 2.0;
+                let phi = y.atan2(x);
+                Some(Vec4::new(-theta.sin() * phi.cos(), -theta.sin() * phi.sin(), -theta.cos(), 0.0))
             }
+            _ => None,
         }
     }
 
     //Renders a scene
+    pub fn render(camera: &Camera, scene: &Scene, canvas: &mut Canvas) {
+        if scene.settings.integrator == Integrator::PathTracer {
+            return Camera::render_path_traced(camera, scene, canvas);
+        }
+        Camera::render_tiles(camera, &scene.settings, canvas, |x, y| {
+            if !Camera::in_view(camera, x, y, 0.5, 0.5) {
+                return None;
+            }
+            let ray = Camera::ray_towards_pixel(camera, x, y);
+            Scene::compute_color(ray, scene, scene.settings.max_depth)
+        });
+    }
+
+    //Renders a scene by averaging several rays through each pixel
+    //The rays are placed by the settings' sampler and weighted by its filter
     pub fn render_supersampled(camera: &Camera, scene: &Scene, canvas: &mut Canvas) {
-        let mut counter = 0;
-        let mut percent = 0;
-        let pixels = &camera.hsize * &camera.vsize;
-        let percentage_update = pixels as f32 / 10.0;
-        for y in 0..camera.vsize {
-            for x in 0..camera.hsize {
-                let ray1 = Camera::ray_towards_pixel(camera, x, y);
-                let ray2 = Camera::ray_towards_pixel_raw(camera, x, y, 0.0, 0.0);
-                let ray3 = Camera::ray_towards_pixel_raw(camera, x, y, 0.0, 1.0);
-                let ray4 = Camera::ray_towards_pixel_raw(camera, x, y, 1.0, 0.0);
-                let ray5 = Camera::ray_towards_pixel_raw(camera, x, y, 1.0, 1.0);
-                let mut list = vec![
-                    Scene::compute_color(ray1, scene, 5),
-                    Scene::compute_color(ray2, scene, 5),
-                    Scene::compute_color(ray3, scene, 5),
-                    Scene::compute_color(ray4, scene, 5),
-                    Scene::compute_color(ray5, scene, 5),
-                ];
-                let mut result = Color::new(0.0, 0.0, 0.0);
-                for _ in 0..5 {
-                    if list[0] != None {
-                        result = result + list.remove(0).unwrap();
-                    } else {
-                        list.remove(0);
-                    }
-                }
-                result = result * 0.2;
-                canvas.set(result, x, y);
-                if counter as f32 > percentage_update {
-                    percent += 10;
-                    println!("Render is {}% complete", percent);
-                    counter = 0;
-                } else {
-                    counter += 1;
-                }
+        if scene.settings.integrator == Integrator::PathTracer {
+            return Camera::render_path_traced(camera, scene, canvas);
+        }
+        Camera::render_tiles(camera, &scene.settings, canvas, |x, y| {
+            Some(Camera::filtered_color(camera, &scene.settings, x, y, |ray| {
+                Scene::compute_color(ray, scene, scene.settings.max_depth).unwrap_or(BLACK)
+            }))
+        });
+    }
+
+    //Renders a scene by path tracing, averaging paths sent through random points in each pixel
+    pub fn render_path_traced(camera: &Camera, scene: &Scene, canvas: &mut Canvas) {
+        Camera::render_tiles(camera, &scene.settings, canvas, |x, y| {
+            Some(Camera::filtered_color(camera, &scene.settings, x, y, |ray| path_trace(ray, scene, &mut LocalRng)))
+        });
+    }
+
+    //Finds the color of a pixel from the weighted average of several rays
+    //Filters with negative lobes can leave no weight, in which case the rays are averaged evenly
+    fn filtered_color<F>(camera: &Camera, settings: &RenderSettings, x: i32, y: i32, trace: F) -> Color
+    where
+        F: Fn(Ray) -> Color,
+    {
+        let offsets = settings.sampler.offsets(settings.samples, &mut LocalRng);
+        let mut weighted = BLACK;
+        let mut plain = BLACK;
+        let mut total_weight = 0.0;
+        let samples = settings.filter.place(&offsets);
+        for (offset_x, offset_y, weight) in &samples {
+            let color = if Camera::in_view(camera, x, y, *offset_x, *offset_y) {
+                trace(Camera::ray_towards_pixel_raw(camera, x, y, *offset_x, *offset_y))
+            }
+            else {
+                BLACK
+            };
+            weighted = weighted + &color * *weight;
+            plain = plain + color;
+            total_weight += weight;
+        }
+        if total_weight > 0.0 {
+            weighted * (1.0 / total_weight)
+        }
+        else {
+            plain * (1.0 / samples.len() as f32)
+        }
+    }
+
+    //Renders a scene with one ray per pixel, then sends more rays through pixels on edges
+    //A pixel is refined when it differs from a neighbor by more than the settings' adaptive threshold, and is split into
+    //quarters while the rays at the corners of each part still differ, up to the settings' adaptive depth
+    //Returns a heatmap of how many rays were sent through each pixel, from blue (fewest) to red (most)
+    pub fn render_adaptive(camera: &Camera, scene: &Scene, canvas: &mut Canvas) -> Canvas {
+        let settings = &scene.settings;
+        Camera::render_tiles(camera, settings, canvas, |x, y| {
+            Some(Camera::trace(camera, scene, x, y, 0.5, 0.5))
+        });
+
+        let first = canvas.clone();
+        let counts: Vec<AtomicUsize> = (0..first.contents.len()).map(|_| AtomicUsize::new(1)).collect();
+        Camera::render_tiles(camera, settings, canvas, |x, y| {
+            let center = first.get(x, y).unwrap();
+            let edge = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|(dx, dy)| match first.get(x + dx, y + dy) {
+                Some(neighbor) => Camera::contrast(center, neighbor) > settings.adaptive_threshold,
+                None => false,
+            });
+            if !edge || settings.adaptive_depth == 0 {
+                return None;
+            }
+            let mut corners = HashMap::new();
+            let color = Camera::subdivide(camera, scene, x, y, (0, 0), 1, &mut corners);
+            counts[y as usize * first.width + x as usize].fetch_add(corners.len(), Ordering::Relaxed);
+            Some(color)
+        });
+
+        let counts: Vec<usize> = counts.into_iter().map(|count| count.into_inner()).collect();
+        let most = counts.iter().copied().max().unwrap_or(1);
+        let mut heatmap = Canvas::new(first.width, first.height);
+        for (index, count) in counts.iter().enumerate() {
+            let amount = if most > 1 { (count - 1) as f32 / (most - 1) as f32 } else { 0.0 };
+            heatmap.contents[index] = Color::new(amount, 0.0, 1.0 - amount);
+        }
+        heatmap
+    }
+
+    //Finds the color of part of a pixel by averaging the rays at its corners, splitting it into quarters while they differ
+    //Parts at a depth are numbered along a grid with 2^(depth - 1) parts on each side of the pixel
+    //Corner colors are shared between neighbouring parts, keyed by their position on the finest grid
+    fn subdivide(
+        camera: &Camera,
+        scene: &Scene,
+        x: i32,
+        y: i32,
+        corner: (usize, usize),
+        depth: usize,
+        corners: &mut HashMap<(usize, usize), Color>,
+    ) -> Color {
+        let max_depth = scene.settings.adaptive_depth;
+        let cells = 1 << max_depth;
+        let size = cells >> (depth - 1);
+        let (u, v) = (corner.0 * size, corner.1 * size);
+        let mut colors = vec![];
+        for key in [(u, v), (u + size, v), (u, v + size), (u + size, v + size)].iter() {
+            let color = corners.entry(*key).or_insert_with(|| {
+                Camera::trace(camera, scene, x, y, key.0 as f32 / cells as f32, key.1 as f32 / cells as f32)
+            });
+            colors.push(color.clone());
+        }
+        let differs = colors.iter().any(|color| Camera::contrast(color, &colors[0]) > scene.settings.adaptive_threshold);
+        if differs && depth < max_depth {
+            let (i, j) = (corner.0 * 2, corner.1 * 2);
+            let mut result = BLACK;
+            for quarter in [(i, j), (i + 1, j), (i, j + 1), (i + 1, j + 1)].iter() {
+                result = result + Camera::subdivide(camera, scene, x, y, *quarter, depth + 1, corners);
             }
+            result * 0.25
+        }
+        else {
+            colors.into_iter().fold(BLACK, |total, color| total + color) * 0.25
+        }
+    }
+
+    //Finds the color seen by a single ray through a point in a pixel using the settings' integrator
+    fn trace(camera: &Camera, scene: &Scene, x: i32, y: i32, offset_x: f32, offset_y: f32) -> Color {
+        if !Camera::in_view(camera, x, y, offset_x, offset_y) {
+            return BLACK;
+        }
+        let ray = Camera::ray_towards_pixel_raw(camera, x, y, offset_x, offset_y);
+        match scene.settings.integrator {
+            Integrator::Whitted => Scene::compute_color(ray, scene, scene.settings.max_depth).unwrap_or(BLACK),
+            Integrator::PathTracer => path_trace(ray, scene, &mut LocalRng),
         }
     }
 
+    //Finds the largest difference between the channels of two colors
+    fn contrast(first: &Color, second: &Color) -> f32 {
+        (first.0 - second.0).abs().max((first.1 - second.1).abs()).max((first.2 - second.2).abs())
+    }
+
     //Renders a scene without lighting
     pub fn quick_render(camera: &Camera, scene: &mut Scene, canvas: &mut Canvas) {
-        let mut counter = 0;
-        let mut percent = 0;
-        let pixels = &camera.hsize * &camera.vsize;
-        let percentage_update = pixels as f32 / 10.0;
-        for y in 0..camera.vsize {
-            for x in 0..camera.hsize {
-                let ray = Camera::ray_towards_pixel(camera, x, y);
-                let color = Scene::compute_color_quick(ray, scene);
-                if color != None {
-                    canvas.set(color.unwrap().clone(), x, y);
-                    if counter as f32 > percentage_update {
-                        percent += 10;
-                        println!("Render is {}% complete", percent);
-                        counter = 0;
-                    } else {
-                        counter += 1;
+        let scene: &Scene = scene;
+        Camera::render_tiles(camera, &scene.settings, canvas, |x, y| {
+            if !Camera::in_view(camera, x, y, 0.5, 0.5) {
+                return None;
+            }
+            let ray = Camera::ray_towards_pixel(camera, x, y);
+            Scene::compute_color_quick(ray, scene)
+        });
+    }
+
+    //Splits the canvas into square tiles (tiles along the right and bottom edges may be smaller)
+    fn tiles(camera: &Camera) -> Vec<Tile> {
+        let mut tiles = vec![];
+        let mut y = 0;
+        while y < camera.vsize {
+            let mut x = 0;
+            while x < camera.hsize {
+                tiles.push(Tile {
+                    x,
+                    y,
+                    width: TILE_SIZE.min(camera.hsize - x),
+                    height: TILE_SIZE.min(camera.vsize - y),
+                });
+                x += TILE_SIZE;
+            }
+            y += TILE_SIZE;
+        }
+        tiles
+    }
+
+    //Shades every pixel of the canvas, spreading tiles across the camera's threads
+    //Each tile covers its own pixels and random numbers are reseeded for every pixel,
+    //so the canvas is the same no matter which thread renders a tile
+    fn render_tiles<F>(camera: &Camera, settings: &RenderSettings, canvas: &mut Canvas, shade: F)
+    where
+        F: Fn(i32, i32) -> Option<Color> + Sync,
+    {
+        let tiles = Camera::tiles(camera);
+        let next_tile = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..settings.threads.max(1) {
+                let sender = sender.clone();
+                let tiles = &tiles;
+                let next_tile = &next_tile;
+                let shade = &shade;
+                scope.spawn(move || loop {
+                    let index = next_tile.fetch_add(1, Ordering::Relaxed);
+                    if index >= tiles.len() {
+                        break;
+                    }
+                    let tile = &tiles[index];
+                    let mut colors = Vec::with_capacity((tile.width * tile.height) as usize);
+                    for y in tile.y..(tile.y + tile.height) {
+                        for x in tile.x..(tile.x + tile.width) {
+                            random::reseed(random::pixel_seed(settings.seed, x, y));
+                            colors.push(shade(x, y));
+                        }
+                    }
+                    if sender.send((index, colors)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+
+            //Finished tiles are written to the canvas as they arrive
+            let start = Instant::now();
+            let mut finished = 0;
+            let mut percent = 0;
+            for (index, colors) in receiver {
+                let tile = &tiles[index];
+                let mut colors = colors.into_iter();
+                for y in tile.y..(tile.y + tile.height) {
+                    for x in tile.x..(tile.x + tile.width) {
+                        if let Some(Some(color)) = colors.next() {
+                            canvas.set(color, x, y);
+                        }
                     }
                 }
+                finished += 1;
+                let current = (finished * 100) / tiles.len();
+                if settings.show_progress && current > percent {
+                    percent = current;
+                    let elapsed = start.elapsed().as_secs_f32();
+                    let remaining = elapsed * (tiles.len() - finished) as f32 / finished as f32;
+                    eprint!("\rRender is {}% complete ({:.1}s elapsed, {:.1}s remaining)   ", percent, elapsed, remaining);
+                }
             }
-        }
+            if settings.show_progress {
+                eprintln!();
+            }
+        });
     }
 }
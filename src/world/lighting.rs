@@ -1,23 +1,128 @@
 use crate::core::color::*;
 use crate::core::comp::Comp;
 use crate::core::matrix::Matrix4x4;
+use crate::core::sequence::Sequence;
 use crate::core::vector::Vec4;
 use crate::ray_tracing::intersection::Intersection;
 use crate::materials::material::*;
+use crate::misc::random::LocalRng;
+use crate::objects::disk::Disk;
+use crate::objects::group::Group;
+use crate::objects::object::Object;
+use crate::objects::sphere::Sphere;
+use crate::objects::triangle::Triangle;
 use crate::ray_tracing::ray::Ray;
+use crate::world::attenuation::Attenuation;
+use crate::world::path_tracing::basis;
 use crate::world::scene::Scene;
-use crate::misc::utils::*;
 use rand::Rng;
+use std::f32::consts::PI;
 
-//A Light is either a PointLight or an AreaLight
-pub trait Light {
+//A Light is either a PointLight, an AreaLight, a SphereLight, a DiskLight, a SpotLight or a DirectionalLight
+pub trait Light: Send + Sync {
     fn get_intensity(&self) -> &Color;
 
     fn get_position(&self) -> &Vec4;
 
     fn get_positions(&self) -> Vec<Vec4>;
 
-    fn light_intensity(&self, point: &Vec4, scene: &Scene) -> f32;
+    //Finds the fraction of the light which gets past the objects between the light and a point at a time in the shutter interval
+    fn transmittance_at(&self, point: &Vec4, scene: &Scene, time: f32) -> Color;
+
+    //Finds the fraction of the light which is sent towards a point, ignoring anything in the way
+    fn falloff_at(&self, _point: &Vec4) -> f32 {
+        1.0
+    }
+
+    //Finds the fraction of the light which reaches a point through the scene as it is at a time in the shutter interval
+    fn light_intensity_at(&self, point: &Vec4, scene: &Scene, time: f32) -> Color {
+        let falloff = self.falloff_at(point);
+        if falloff <= 0.0 {
+            return BLACK;
+        }
+        self.transmittance_at(point, scene, time) * falloff
+    }
+
+    //Finds the fraction of the light which reaches a point when the shutter opens
+    fn light_intensity(&self, point: &Vec4, scene: &Scene) -> Color {
+        self.light_intensity_at(point, scene, 0.0)
+    }
+
+    fn get_attenuation(&self) -> &Attenuation;
+
+    //Finds the fraction of the light's intensity which reaches a point from one of the light's positions
+    fn attenuation(&self, point: &Vec4, light_position: &Vec4) -> f32 {
+        self.get_attenuation().factor(Vec4::magnitude(&(light_position - point)))
+    }
+
+    //Creates a glowing surface showing where the light is, for lights with a size
+    fn geometry(&self) -> Option<Box<dyn Object>> {
+        None
+    }
+
+    //Finds the direction from a point towards one of the light's positions
+    fn light_vector(&self, point: &Vec4, light_position: &Vec4) -> Vec4 {
+        (light_position - point).normalize()
+    }
+}
+
+//The ways points are picked on area lights when testing for shadows
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LightSampling {
+    //The center of each cell, which gives banded but noise free shadows
+    Stratified,
+    //A random point in each cell, which gives smooth but noisy shadows
+    Jittered,
+    //Points from a Halton sequence spread evenly over the whole light, which gives smooth shadows without noise
+    LowDiscrepancy,
+}
+
+//Picks a point in the unit square for each of the usteps by vsteps cells of an area light
+pub fn sample_unit_square(sampling: LightSampling, usteps: usize, vsteps: usize) -> Vec<(f32, f32)> {
+    let count = usteps * vsteps;
+    let mut samples = Vec::with_capacity(count);
+    match sampling {
+        LightSampling::Stratified | LightSampling::Jittered => {
+            let mut rng = LocalRng;
+            for v in 0..vsteps {
+                for u in 0..usteps {
+                    let (du, dv) = if sampling == LightSampling::Jittered { (rng.gen(), rng.gen()) } else { (0.5, 0.5) };
+                    samples.push(((u as f32 + du) / usteps as f32, (v as f32 + dv) / vsteps as f32));
+                }
+            }
+        }
+        LightSampling::LowDiscrepancy if count > 0 => {
+            let mut u_sequence = Sequence::halton(2, count);
+            let mut v_sequence = Sequence::halton(3, count);
+            for _ in 0..count {
+                samples.push((u_sequence.next(), v_sequence.next()));
+            }
+        }
+        LightSampling::LowDiscrepancy => (),
+    }
+    samples
+}
+
+//Averages the light which reaches a point from each position on an area light
+fn average_transmittance(positions: &[Vec4], point: &Vec4, scene: &Scene, time: f32) -> Color {
+    let mut total = BLACK;
+    for light_position in positions {
+        total = total + shadow_transmittance(light_position, point, scene, time);
+    }
+    total * (1.0 / positions.len().max(1) as f32)
+}
+
+//Creates the material of a visible light, which only glows and does not block any light
+pub fn light_material(intensity: &Color) -> Material {
+    let mut material = Material::default();
+    material.color = BLACK;
+    material.ambient = 0.0;
+    material.diffuse = 0.0;
+    material.specular = 0.0;
+    material.casts_shadows = false;
+    material.emission = intensity.clone();
+    material.light_surface = true;
+    material
 }
 
 //An area light is an array of lights which produce soft shadows
@@ -30,6 +135,8 @@ pub struct AreaLight {
     pub vsteps: usize, //Width separation of lights on the v edge
     pub samples: usize,
     pub intensity: Color,
+    pub attenuation: Attenuation, //How the light weakens with distance
+    pub sampling: LightSampling,  //How points on the light are picked for shadows
 }
 
 impl Light for AreaLight {
@@ -37,6 +144,10 @@ impl Light for AreaLight {
         &self.intensity
     }
 
+    fn get_attenuation(&self) -> &Attenuation {
+        &self.attenuation
+    }
+
     fn get_position(&self) -> &Vec4 {
         &self.corner
     }
@@ -51,17 +162,25 @@ impl Light for AreaLight {
         vec
     }
 
-    fn light_intensity(&self, point: &Vec4, scene: &Scene) -> f32 {
-        let mut total = 0.0;
-        for v in 0..self.vsteps {
-            for u in 0..self.usteps {
-                let light_position = self.point_on_light(u, v, true);
-                if !in_shadow(&light_position, point, scene) {
-                    total += 1.0;
-                }
-            }
-        }
-        total / (self.samples as f32)
+    fn transmittance_at(&self, point: &Vec4, scene: &Scene, time: f32) -> Color {
+        let positions: Vec<Vec4> = sample_unit_square(self.sampling, self.usteps, self.vsteps)
+            .into_iter()
+            .map(|(u, v)| self.point_at(u, v))
+            .collect();
+        average_transmittance(&positions, point, scene, time)
+    }
+
+    //The light is shown as a parallelogram made of two triangles
+    fn geometry(&self) -> Option<Box<dyn Object>> {
+        let material = light_material(&self.intensity);
+        let p1 = self.point_at(0.0, 0.0);
+        let p2 = self.point_at(1.0, 0.0);
+        let p3 = self.point_at(1.0, 1.0);
+        let p4 = self.point_at(0.0, 1.0);
+        let mut group = Group::new(Matrix4x4::identity(), material.clone());
+        Triangle::new(p1.clone(), p2, p3.clone(), material.clone()).add_to_group(&mut group);
+        Triangle::new(p1, p3, p4, material).add_to_group(&mut group);
+        Some(Box::new(group))
     }
 }
 
@@ -83,28 +202,183 @@ impl AreaLight {
             vsteps: vsteps as usize,
             samples: (&vsteps * &usteps) as usize,
             intensity,
+            attenuation: Attenuation::default(),
+            sampling: LightSampling::Jittered,
         }
     }
 
+    //Finds the point in a cell of the light, either at its center or a random point inside it
     pub fn point_on_light(&self, u: usize, v: usize, jitter: bool) -> Vec4 {
-        let mut rng = rand::thread_rng();
-        if jitter {
-            &self.corner
-            + &self.uvec * ((u as f32) + rng.gen_range(-0.5, 0.5))
-            + &self.vvec * ((v as f32) + rng.gen_range(-0.5, 0.5))
+        let mut rng = LocalRng;
+        let (du, dv) = if jitter { (rng.gen(), rng.gen()) } else { (0.5, 0.5) };
+        &self.corner + &self.uvec * ((u as f32) + du) + &self.vvec * ((v as f32) + dv)
+    }
+
+    //Finds the point on the light from coordinates between 0 and 1 along each edge
+    pub fn point_at(&self, u: f32, v: f32) -> Vec4 {
+        &self.corner + &self.uvec * (u * self.usteps as f32) + &self.vvec * (v * self.vsteps as f32)
+    }
+}
+
+//Light given off by the surface of a sphere
+#[derive(Debug, PartialEq)]
+pub struct SphereLight {
+    pub center: Vec4,
+    pub radius: f32,
+    pub usteps: usize, //Number of cells around the sphere
+    pub vsteps: usize, //Number of cells from the bottom to the top of the sphere
+    pub intensity: Color,
+    pub attenuation: Attenuation, //How the light weakens with distance
+    pub sampling: LightSampling,  //How points on the light are picked for shadows
+}
+
+impl Light for SphereLight {
+    fn get_intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    fn get_attenuation(&self) -> &Attenuation {
+        &self.attenuation
+    }
+
+    fn get_position(&self) -> &Vec4 {
+        &self.center
+    }
+
+    fn get_positions(&self) -> Vec<Vec4> {
+        sample_unit_square(LightSampling::Stratified, self.usteps, self.vsteps)
+            .into_iter()
+            .map(|(u, v)| self.point_at(u, v))
+            .collect()
+    }
+
+    fn transmittance_at(&self, point: &Vec4, scene: &Scene, time: f32) -> Color {
+        let positions: Vec<Vec4> = sample_unit_square(self.sampling, self.usteps, self.vsteps)
+            .into_iter()
+            .map(|(u, v)| self.point_at(u, v))
+            .collect();
+        average_transmittance(&positions, point, scene, time)
+    }
+
+    fn geometry(&self) -> Option<Box<dyn Object>> {
+        let transform = Matrix4x4::translation(self.center.0, self.center.1, self.center.2) * Matrix4x4::scaling(self.radius, self.radius, self.radius);
+        Some(Box::new(Sphere::new(transform, light_material(&self.intensity))))
+    }
+}
+
+impl SphereLight {
+    //Creates a new SphereLight
+    pub fn new(center: Vec4, radius: f32, usteps: usize, vsteps: usize, intensity: Color) -> SphereLight {
+        SphereLight {
+            center,
+            radius,
+            usteps,
+            vsteps,
+            intensity,
+            attenuation: Attenuation::default(),
+            sampling: LightSampling::Jittered,
         }
-        else {
-            &self.corner
-            + &self.uvec * ((u as f32) + 0.5)
-            + &self.vvec * ((v as f32) + 0.5)
+    }
+
+    //Finds the point on the sphere from coordinates between 0 and 1, where equal areas of u and v cover equal areas of the sphere
+    pub fn point_at(&self, u: f32, v: f32) -> Vec4 {
+        let phi = 2.0 * PI * u;
+        let y = 1.0 - 2.0 * v;
+        let r = (1.0 - y * y).max(0.0).sqrt();
+        &self.center + Vec4::new(r * phi.cos(), y, r * phi.sin(), 0.0) * self.radius
+    }
+}
+
+//Light given off by one side of a flat disk
+#[derive(Debug, PartialEq)]
+pub struct DiskLight {
+    pub center: Vec4,
+    pub normal: Vec4, //Direction the disk faces
+    pub radius: f32,
+    pub usteps: usize, //Number of cells around the disk
+    pub vsteps: usize, //Number of cells from the center to the edge of the disk
+    pub intensity: Color,
+    pub attenuation: Attenuation, //How the light weakens with distance
+    pub sampling: LightSampling,  //How points on the light are picked for shadows
+}
+
+impl Light for DiskLight {
+    fn get_intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    fn get_attenuation(&self) -> &Attenuation {
+        &self.attenuation
+    }
+
+    fn get_position(&self) -> &Vec4 {
+        &self.center
+    }
+
+    fn get_positions(&self) -> Vec<Vec4> {
+        sample_unit_square(LightSampling::Stratified, self.usteps, self.vsteps)
+            .into_iter()
+            .map(|(u, v)| self.point_at(u, v))
+            .collect()
+    }
+
+    //The disk only shines from its front, so points behind it are dark
+    fn falloff_at(&self, point: &Vec4) -> f32 {
+        if Vec4::dot(&(point - &self.center), &self.normal) <= 0.0 { 0.0 } else { 1.0 }
+    }
+
+    fn transmittance_at(&self, point: &Vec4, scene: &Scene, time: f32) -> Color {
+        let positions: Vec<Vec4> = sample_unit_square(self.sampling, self.usteps, self.vsteps)
+            .into_iter()
+            .map(|(u, v)| self.point_at(u, v))
+            .collect();
+        average_transmittance(&positions, point, scene, time)
+    }
+
+    //The light is shown as a one-sided disk with its y axis along the normal, so it is only seen from the side it lights
+    fn geometry(&self) -> Option<Box<dyn Object>> {
+        //The axes are ordered so the transform does not mirror the disk
+        let (z_axis, x_axis) = basis(&self.normal);
+        let r = self.radius;
+        let transform = Matrix4x4::new(
+            (x_axis.0 * r, self.normal.0, z_axis.0 * r, self.center.0),
+            (x_axis.1 * r, self.normal.1, z_axis.1 * r, self.center.1),
+            (x_axis.2 * r, self.normal.2, z_axis.2 * r, self.center.2),
+            (0.0, 0.0, 0.0, 1.0),
+        );
+        Some(Box::new(Disk::new(transform, light_material(&self.intensity))))
+    }
+}
+
+impl DiskLight {
+    //Creates a new DiskLight
+    pub fn new(center: Vec4, normal: Vec4, radius: f32, usteps: usize, vsteps: usize, intensity: Color) -> DiskLight {
+        DiskLight {
+            center,
+            normal: normal.normalize(),
+            radius,
+            usteps,
+            vsteps,
+            intensity,
+            attenuation: Attenuation::default(),
+            sampling: LightSampling::Jittered,
         }
     }
+
+    //Finds the point on the disk from coordinates between 0 and 1, where equal areas of u and v cover equal areas of the disk
+    pub fn point_at(&self, u: f32, v: f32) -> Vec4 {
+        let (x_axis, y_axis) = basis(&self.normal);
+        let angle = 2.0 * PI * u;
+        let r = self.radius * v.sqrt();
+        &self.center + x_axis * (r * angle.cos()) + y_axis * (r * angle.sin())
+    }
 }
 
 //Light in space with no size
 #[derive(Debug, PartialEq)]
 pub struct PointLight {
     pub intensity: Color,
+    pub attenuation: Attenuation, //How the light weakens with distance
     pub position: Vec4,
 }
 
@@ -113,6 +387,10 @@ impl Light for PointLight {
         &self.intensity
     }
 
+    fn get_attenuation(&self) -> &Attenuation {
+        &self.attenuation
+    }
+
     fn get_position(&self) -> &Vec4 {
         &self.position
     }
@@ -121,13 +399,9 @@ impl Light for PointLight {
         vec![self.position.clone()]
     }
 
-    //Finds the intensity of a PointLight at a given point
-    fn light_intensity(&self, point: &Vec4, scene: &Scene) -> f32 {
-        if in_shadow(&self.position, point, scene) == true {
-            1.0
-        } else {
-            1.0
-        }
+    //Finds the intensity of a PointLight at a given point (black in shadow and white when lit)
+    fn transmittance_at(&self, point: &Vec4, scene: &Scene, time: f32) -> Color {
+        shadow_transmittance(&self.position, point, scene, time)
     }
 }
 
@@ -136,7 +410,127 @@ impl PointLight {
     pub fn new(intensity: Color, position: Vec4) -> PointLight {
         PointLight {
             intensity,
+            attenuation: Attenuation::default(),
+            position,
+        }
+    }
+}
+
+//Light shining from a point in a cone, which fades out between the inner and outer angles
+#[derive(Debug, PartialEq)]
+pub struct SpotLight {
+    pub intensity: Color,
+    pub attenuation: Attenuation, //How the light weakens with distance
+    pub position: Vec4,
+    pub direction: Vec4,  //Direction the center of the cone points in
+    pub inner_angle: f32, //Angle from the center in degrees where the light starts fading
+    pub outer_angle: f32, //Angle from the center in degrees where the light is gone
+}
+
+impl Light for SpotLight {
+    fn get_intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    fn get_attenuation(&self) -> &Attenuation {
+        &self.attenuation
+    }
+
+    fn get_position(&self) -> &Vec4 {
+        &self.position
+    }
+
+    fn get_positions(&self) -> Vec<Vec4> {
+        vec![self.position.clone()]
+    }
+
+    //Points outside of the cone are dark
+    fn falloff_at(&self, point: &Vec4) -> f32 {
+        self.falloff(point)
+    }
+
+    fn transmittance_at(&self, point: &Vec4, scene: &Scene, time: f32) -> Color {
+        shadow_transmittance(&self.position, point, scene, time)
+    }
+}
+
+impl SpotLight {
+    //Creates a new SpotLight, making sure the outer angle is not inside the inner angle
+    pub fn new(intensity: Color, position: Vec4, direction: Vec4, inner_angle: f32, outer_angle: f32) -> SpotLight {
+        SpotLight {
+            intensity,
+            attenuation: Attenuation::default(),
             position,
+            direction: direction.normalize(),
+            inner_angle,
+            outer_angle: outer_angle.max(inner_angle),
+        }
+    }
+
+    //Finds how much of the light reaches a point based on its angle from the center of the cone
+    //The light fades smoothly from 1.0 at the inner angle to 0.0 at the outer angle
+    pub fn falloff(&self, point: &Vec4) -> f32 {
+        let cos_angle = Vec4::dot(&(point - &self.position).normalize(), &self.direction);
+        let cos_inner = self.inner_angle.to_radians().cos();
+        let cos_outer = self.outer_angle.to_radians().cos();
+        if cos_angle >= cos_inner {
+            return 1.0;
+        }
+        if cos_angle <= cos_outer {
+            return 0.0;
+        }
+        let t = (cos_angle - cos_outer) / (cos_inner - cos_outer);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+//Infinitely distant light, like the sun, whose rays are all parallel
+#[derive(Debug, PartialEq)]
+pub struct DirectionalLight {
+    pub intensity: Color,
+    pub direction: Vec4, //Direction the light travels in
+}
+
+impl Light for DirectionalLight {
+    fn get_intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    //Directional lights are infinitely far away, so they can not weaken with distance
+    fn get_attenuation(&self) -> &Attenuation {
+        &Attenuation::None
+    }
+
+    fn attenuation(&self, _point: &Vec4, _light_position: &Vec4) -> f32 {
+        1.0
+    }
+
+    //Directional lights have no position, so their direction is used instead
+    fn get_position(&self) -> &Vec4 {
+        &self.direction
+    }
+
+    fn get_positions(&self) -> Vec<Vec4> {
+        vec![self.direction.clone()]
+    }
+
+    //Finds the intensity of a DirectionalLight at a given point, checking for shadows all the way back along its direction
+    fn transmittance_at(&self, point: &Vec4, scene: &Scene, time: f32) -> Color {
+        transmittance(point, &self.direction.negate(), f32::INFINITY, scene, time)
+    }
+
+    //Light arrives from the same direction at every point
+    fn light_vector(&self, _point: &Vec4, _light_position: &Vec4) -> Vec4 {
+        self.direction.negate()
+    }
+}
+
+impl DirectionalLight {
+    //Creates a new DirectionalLight
+    pub fn new(intensity: Color, direction: Vec4) -> DirectionalLight {
+        DirectionalLight {
+            intensity,
+            direction: direction.normalize(),
         }
     }
 }
@@ -158,7 +552,8 @@ pub fn reflected_color(
                 1.0,
             ),
             Vec4::new(comps.r_vec.0, comps.r_vec.1, comps.r_vec.2, 0.0),
-        );
+        )
+        .at_time(comps.time);
         let color = Scene::compute_color(reflected_ray, scene, remaining - 1);
         if color != None {
             color.unwrap() * comps.material.reflectivity
@@ -183,7 +578,7 @@ pub fn refracted_color(scene: &Scene, comps: &Comp, remaining: i32) -> Color {
     }
     let cos_t = (1.0 - sin2_t).sqrt();
     let direction = (&comps.n_vec * (n_ratio * cos_i - cos_t)) - (&comps.e_vec * n_ratio);
-    let refract_ray = Ray::new_from_vec(comps.under_point.clone(), direction);
+    let refract_ray = Ray::new_from_vec(comps.under_point.clone(), direction).at_time(comps.time);
     let color = Scene::compute_color(refract_ray, scene, remaining - 1);
     if color != None {
         color.unwrap() * comps.material.transparency
@@ -192,12 +587,13 @@ pub fn refracted_color(scene: &Scene, comps: &Comp, remaining: i32) -> Color {
     }
 }
 
-//Finds the refracted color at a certain point
+//Finds the color reflected onto a point by nearby objects
+//Only rays from the camera (which have every bounce remaining) receive environment lighting
 pub fn environment_color(scene: &Scene, comps: &Comp, remaining: i32) -> Color {
-    if remaining < 5 || comps.material.environment_lighting == 0.0 {
+    if remaining < scene.settings.max_depth || remaining <= 0 || comps.material.environment_lighting == 0.0 {
         return BLACK;
     }
-    let new_ray = Ray::new_from_vec(comps.over_point.clone(), comps.n_vec.clone());
+    let new_ray = Ray::new_from_vec(comps.over_point.clone(), comps.n_vec.clone()).at_time(comps.time);
     let intersections = Ray::intersect_scene(&scene, new_ray.clone());
     let hit = Intersection::hit(&intersections);
     let factor;
@@ -250,7 +646,7 @@ pub fn lighting(
     point: &Vec4,
     e_vec: &Vec4,
     n_vec: &Vec4,
-    light_intensity: f32,
+    light_intensity: &Color,
     list: &Vec<Matrix4x4>
 ) -> Color {
     let mut color = material.color.clone();
@@ -270,18 +666,21 @@ pub fn lighting(
 
     let mut diffuse_sum = BLACK;
     let mut specular_sum = BLACK;
+    let light_intensity = light_intensity.clamp_range(0.0, 1.0);
 
     //Iterate through lights
     for light_position in light.get_positions() {
         //Finds the direction to the light source
-        let light_vec = (light_position - point).normalize();
+        let light_vec = light.light_vector(point, &light_position);
 
         //light_dot_normal represents the cosine between the light and normal vectors
         let light_dot_normal = Vec4::dot(&light_vec, &n_vec);
 
         //A negative light_dot_normal means the light is obstructed
         if light_dot_normal >= 0.0 {
-            diffuse_sum = diffuse_sum + (&effective_color * material.diffuse * light_dot_normal * clamp_float(light_intensity, 0.4, 1.0));
+            //Light from far away positions is weakened
+            let attenuation = light.attenuation(point, &light_position);
+            diffuse_sum = diffuse_sum + (&effective_color * (material.diffuse * light_dot_normal * attenuation) * &light_intensity);
 
             //reflect_dot_eye represents the cosine of the angle between the reflection and eye vectors
             let reflect_vec = Vec4::reflect(&light_vec.negate(), &n_vec);
@@ -290,7 +689,7 @@ pub fn lighting(
             if reflect_dot_eye > 0.0 {
                 let factor = f32::powf(reflect_dot_eye as f32, material.shininess);
                 specular_sum = specular_sum
-                    + light.get_intensity() * &material.specular * factor * clamp_float(light_intensity, 0.4, 1.0);
+                    + light.get_intensity() * (material.specular * factor * attenuation) * &light_intensity;
             }
         }
     }
@@ -299,20 +698,37 @@ pub fn lighting(
     ambient + (diffuse_sum * light_count) + (specular_sum * light_count)
 }
 
-//Creates a vector from a point to a given light and tests for intersections within that distance
+//Tests if the light from a given position is completely blocked before reaching a point
 pub fn in_shadow(light_position: &Vec4, point: &Vec4, scene: &Scene) -> bool {
+    shadow_transmittance(light_position, point, scene, 0.0) == BLACK
+}
+
+//Finds the fraction of each color channel of light which reaches a point from a given position
+//Every surface between the point and the light filters the light by its color and transparency,
+//opaque surfaces block it completely and objects which do not cast shadows are ignored
+pub fn shadow_transmittance(light_position: &Vec4, point: &Vec4, scene: &Scene, time: f32) -> Color {
     let vector = light_position - point;
-    let distance = Vec4::magnitude(&vector);
-    let direction = (&vector).normalize();
-    let shadow_ray = Ray::new_from_vec(Vec4::new(point.0, point.1, point.2, 1.0), direction);
+    transmittance(point, &vector.normalize(), Vec4::magnitude(&vector), scene, time)
+}
+
+//Finds the fraction of light which reaches a point from a given direction, only counting surfaces within a distance
+pub fn transmittance(point: &Vec4, direction: &Vec4, distance: f32, scene: &Scene, time: f32) -> Color {
+    let shadow_ray = Ray::new_from_vec(Vec4::new(point.0, point.1, point.2, 1.0), direction.clone()).at_time(time);
+    let mut transmittance = WHITE;
     let intersections = Ray::intersect_scene(scene, shadow_ray);
-    let ray_hit = Intersection::hit(&intersections);
-    let mut result = false;
-    if !ray_hit.is_none() {
-        let unwrapped = ray_hit.unwrap();
-        if unwrapped.t < distance && unwrapped.object.get_material().casts_shadows == true {
-            result = true;
+    for intersection in intersections.iter().filter(|i| i.t > 0.0 && i.t < distance) {
+        //Shadows are filtered by the same material the surface is shaded with
+        let material = intersection.object.get_effective_material();
+        if !material.casts_shadows {
+            continue;
+        }
+        if material.transparency <= 0.0 || !scene.settings.transparent_shadows {
+            return BLACK;
+        }
+        transmittance = transmittance * (&material.color * material.transparency).clamp_range(0.0, 1.0);
+        if transmittance == BLACK {
+            return BLACK;
         }
     }
-    result
+    transmittance
 }
@@ -1,3 +1,11 @@
+pub mod animation;
+pub mod antialiasing;
+pub mod attenuation;
+pub mod background;
 pub mod camera;
 pub mod lighting;
-pub mod scene;
\ No newline at end of file
+pub mod path_tracing;
+pub mod scene;
+pub mod scene_file;
+pub mod settings;
+pub mod stereo;
\ No newline at end of file
@@ -0,0 +1,203 @@
+use crate::core::color::*;
+use crate::core::comp::Comp;
+use crate::core::vector::Vec4;
+use crate::ray_tracing::intersection::Intersection;
+use crate::ray_tracing::ray::Ray;
+use crate::world::lighting::schlick;
+use crate::world::scene::Scene;
+use rand::Rng;
+use std::f32::consts::PI;
+
+//Number of bounces before paths may be ended early by russian roulette
+const ROULETTE_DEPTH: i32 = 3;
+
+//The ways light can scatter off of a surface
+//Material properties are reused as the weight of each lobe:
+//diffuse for the diffuse lobe, specular and shininess for the glossy lobe,
+//reflectivity for perfect mirrors and transparency for refraction
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Lobe {
+    Diffuse,
+    Glossy,
+    Mirror,
+    Transmission,
+}
+
+//Follows a ray as it randomly bounces around the scene and returns the light carried back along it
+//Scene lights are sampled directly at each bounce, emissive objects add light when hit
+//and paths which escape the scene see the background
+pub fn path_trace<R: Rng>(ray: Ray, scene: &Scene, rng: &mut R) -> Color {
+    let mut radiance = BLACK;
+    let mut throughput = WHITE;
+    let mut ray = ray;
+    //Visible light surfaces are only counted when they can not have been sampled directly at the last bounce
+    let mut specular = true;
+    for bounce in 0..=scene.settings.max_depth.max(0) {
+        let intersections = Ray::intersect_scene(scene, ray.clone());
+        let hit = match Intersection::hit(&intersections) {
+            Some(hit) => hit,
+            None => {
+                radiance = radiance + &throughput * scene.background.color_at(&ray.direction);
+                break;
+            }
+        };
+        let comps = Comp::compute_vars(hit, &ray, &intersections);
+        let color = surface_color(&comps);
+        if specular || !comps.material.light_surface {
+            radiance = radiance + &throughput * &comps.material.emission;
+        }
+        radiance = radiance + &throughput * direct_lighting(scene, &comps, &color);
+
+        //Russian roulette ends dim paths early, boosting the paths which survive to keep the result unbiased
+        if bounce >= ROULETTE_DEPTH {
+            let survival = throughput.0.max(throughput.1).max(throughput.2).min(0.95);
+            if rng.gen::<f32>() >= survival {
+                break;
+            }
+            throughput = throughput * (1.0 / survival);
+        }
+
+        let (lobe, weight) = match choose_lobe(&comps, rng) {
+            Some(choice) => choice,
+            None => break,
+        };
+        specular = lobe == Lobe::Mirror || lobe == Lobe::Transmission;
+        let next = match lobe {
+            Lobe::Diffuse => {
+                throughput = throughput * color * weight;
+                let direction = sample_cosine_hemisphere(&comps.n_vec, rng);
+                Ray::new_from_vec(comps.over_point.clone(), direction)
+            }
+            Lobe::Glossy => {
+                let direction = sample_phong_lobe(&comps.r_vec, comps.material.shininess, rng);
+                let cos = Vec4::dot(&direction, &comps.n_vec);
+                //Directions sampled below the surface carry no light
+                if cos <= 0.0 {
+                    break;
+                }
+                let exponent = comps.material.shininess;
+                throughput = throughput * (weight * cos * (exponent + 2.0) / (exponent + 1.0));
+                Ray::new_from_vec(comps.over_point.clone(), direction)
+            }
+            Lobe::Mirror => {
+                throughput = throughput * weight;
+                Ray::new_from_vec(comps.over_point.clone(), comps.r_vec.clone())
+            }
+            Lobe::Transmission => {
+                throughput = throughput * weight;
+                //Fresnel reflection is chosen randomly in proportion to its strength
+                match refraction_direction(&comps) {
+                    Some(direction) if rng.gen::<f32>() >= schlick(&comps) => Ray::new_from_vec(comps.under_point.clone(), direction),
+                    _ => Ray::new_from_vec(comps.over_point.clone(), comps.r_vec.clone()),
+                }
+            }
+        };
+        ray = next.at_time(ray.time);
+    }
+    radiance
+}
+
+//Finds the color of a surface including its pattern
+fn surface_color(comps: &Comp) -> Color {
+    match &comps.material.pattern {
+        Some(pattern) => pattern.color_at_object(&comps.parent_inverses, &comps.object_inverse, &comps.over_point),
+        None => comps.material.color.clone(),
+    }
+}
+
+//Picks the lobe a path scatters into and the factor the path's throughput is scaled by
+//Lobes are picked in proportion to their weights, which are scaled down if they add up to more than 1
+pub fn choose_lobe<R: Rng>(comps: &Comp, rng: &mut R) -> Option<(Lobe, f32)> {
+    let material = &comps.material;
+    let lobes = [
+        (Lobe::Diffuse, material.diffuse.max(0.0)),
+        (Lobe::Glossy, material.specular.max(0.0)),
+        (Lobe::Mirror, material.reflectivity.max(0.0)),
+        (Lobe::Transmission, material.transparency.max(0.0)),
+    ];
+    let total: f32 = lobes.iter().map(|(_, weight)| weight).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut choice = rng.gen::<f32>() * total;
+    for (lobe, weight) in lobes.iter() {
+        if choice < *weight {
+            return Some((*lobe, total.min(1.0)));
+        }
+        choice -= weight;
+    }
+    None
+}
+
+//Adds up the light arriving directly from each scene light through the diffuse and glossy lobes
+//Light intensities are treated as the irradiance they give a surface facing them so paths match Phong brightness
+fn direct_lighting(scene: &Scene, comps: &Comp, color: &Color) -> Color {
+    let material = &comps.material;
+    let total = material.diffuse.max(0.0) + material.specular.max(0.0) + material.reflectivity.max(0.0) + material.transparency.max(0.0);
+    let scale = if total > 1.0 { 1.0 / total } else { 1.0 };
+    let mut result = BLACK;
+    for light in &scene.light_sources {
+        let visibility = light.light_intensity_at(&comps.over_point, scene, comps.time);
+        if visibility == BLACK {
+            continue;
+        }
+        let positions = light.get_positions();
+        let mut sum = BLACK;
+        for position in &positions {
+            let light_vec = light.light_vector(&comps.over_point, position);
+            let cos = Vec4::dot(&light_vec, &comps.n_vec);
+            if cos <= 0.0 {
+                continue;
+            }
+            let diffuse = color * (material.diffuse.max(0.0) * cos);
+            //Normalized Phong lobe so glossy surfaces reflect no more light than they receive
+            let alignment = Vec4::dot(&light_vec, &comps.r_vec).max(0.0);
+            let glossy = material.specular.max(0.0) * (material.shininess + 2.0) / 2.0 * alignment.powf(material.shininess) * cos;
+            sum = sum + (diffuse + WHITE * glossy) * light.attenuation(&comps.over_point, position);
+        }
+        result = result + sum * light.get_intensity() * visibility * (scale / positions.len() as f32);
+    }
+    result
+}
+
+//Finds the direction a ray bends in when passing into a transparent object, or None for total internal reflection
+fn refraction_direction(comps: &Comp) -> Option<Vec4> {
+    let n_ratio = comps.n1 / comps.n2;
+    let cos_i = Vec4::dot(&comps.e_vec, &comps.n_vec);
+    let sin2_t = (n_ratio.powi(2)) * (1.0 - (cos_i.powi(2)));
+    if sin2_t > 1.0 {
+        return None;
+    }
+    let cos_t = (1.0 - sin2_t).sqrt();
+    Some(((&comps.n_vec * (n_ratio * cos_i - cos_t)) - (&comps.e_vec * n_ratio)).normalize())
+}
+
+//Creates two vectors which form a right angled basis with a given vector
+pub(crate) fn basis(w: &Vec4) -> (Vec4, Vec4) {
+    let helper = if w.0.abs() > 0.9 { Vec4::new(0.0, 1.0, 0.0, 0.0) } else { Vec4::new(1.0, 0.0, 0.0, 0.0) };
+    let u = (&helper * w).normalize();
+    let v = w * &u;
+    (u, v)
+}
+
+//Turns local coordinates around a vector into a world direction
+fn from_local(w: &Vec4, x: f32, y: f32, z: f32) -> Vec4 {
+    let (u, v) = basis(w);
+    (u * x + v * y + w * z).normalize()
+}
+
+//Picks a direction on the hemisphere around a normal, favouring directions close to the normal
+pub fn sample_cosine_hemisphere<R: Rng>(normal: &Vec4, rng: &mut R) -> Vec4 {
+    let phi = 2.0 * PI * rng.gen::<f32>();
+    let r2: f32 = rng.gen();
+    let r = r2.sqrt();
+    from_local(normal, r * phi.cos(), r * phi.sin(), (1.0 - r2).sqrt())
+}
+
+//Picks a direction around a reflection vector, with higher exponents staying closer to it
+pub fn sample_phong_lobe<R: Rng>(reflection: &Vec4, exponent: f32, rng: &mut R) -> Vec4 {
+    let phi = 2.0 * PI * rng.gen::<f32>();
+    let cos_alpha = rng.gen::<f32>().powf(1.0 / (exponent + 1.0));
+    let sin_alpha = (1.0 - cos_alpha * cos_alpha).sqrt();
+    from_local(&reflection.normalize(), sin_alpha * phi.cos(), sin_alpha * phi.sin(), cos_alpha)
+}
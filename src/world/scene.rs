@@ -1,17 +1,23 @@
-use crate::core::color::Color;
+use crate::core::color::*;
 use crate::core::comp::Comp;
 use crate::core::matrix::Matrix4x4;
 use crate::core::vector::Vec4;
 use crate::objects::object::*;
 use crate::objects::sphere::Sphere;
+use crate::objects::group::Group;
+use crate::objects::csg::CSG;
 use crate::ray_tracing::intersection::Intersection;
 use crate::world::lighting::*;
 use crate::materials::material::Material;
 use crate::ray_tracing::ray::Ray;
+use crate::world::background::Background;
+use crate::world::settings::RenderSettings;
 
 pub struct Scene {
     pub light_sources: Vec<Box<dyn Light>>,
     pub objects: Vec<Box<dyn Object>>,
+    pub settings: RenderSettings,
+    pub background: Background,
 }
 
 impl Scene {
@@ -20,6 +26,50 @@ impl Scene {
         Scene {
             light_sources: vec![],
             objects: vec![],
+            settings: RenderSettings::default(),
+            background: Background::default(),
+        }
+    }
+
+    //Adds a light to the scene, along with a surface showing where it is when visible is true
+    //Only lights with a size (area, sphere and disk lights) can be seen
+    pub fn add_light(&mut self, light: Box<dyn Light>, visible: bool) {
+        if visible {
+            if let Some(geometry) = light.geometry() {
+                self.objects.push(geometry);
+            }
+        }
+        self.light_sources.push(light);
+    }
+
+    //Builds a bounding volume hierarchy over the objects in the scene
+    //Large scenes are gathered into an untransformed group before being split
+    pub fn divide(&mut self, threshold: usize) {
+        if self.objects.len() > threshold {
+            let mut group = Group::default();
+            group.objects = self.objects.drain(..).collect();
+            self.objects.push(Box::new(group));
+        }
+        for object in &mut self.objects {
+            object.divide(threshold);
+        }
+    }
+
+    //Counts the shapes in the scene, looking inside groups and csgs
+    //This stays the same when the scene is divided into a hierarchy
+    pub fn shape_count(&self) -> usize {
+        self.objects.iter().map(|object| Scene::shapes(object.as_ref())).sum()
+    }
+
+    fn shapes(object: &dyn Object) -> usize {
+        if let Some(group) = object.as_any().downcast_ref::<Group>() {
+            group.objects.iter().map(|child| Scene::shapes(child.as_ref())).sum()
+        }
+        else if let Some(csg) = object.as_any().downcast_ref::<CSG>() {
+            csg.objects.iter().map(|child| Scene::shapes(child.as_ref())).sum()
+        }
+        else {
+            1
         }
     }
 
@@ -29,9 +79,18 @@ impl Scene {
         comps: &Comp,
         remaining: i32,
     ) -> Color {
-        let mut color = Color::new(0.0, 0.0, 0.0);
+        //Emissive surfaces, like visible lights, glow with their own light
+        let mut color = comps.material.emission.clone();
         for light in &scene.light_sources {
-            let light_intensity = light.light_intensity(&comps.over_point, &scene);
+            //Shadows are lifted to the shadow floor so they are never darker than the settings allow
+            //Only the shadows are lifted, so points outside a spot light's cone or behind a disk light stay dark
+            let falloff = light.falloff_at(&comps.over_point);
+            let light_intensity = if falloff <= 0.0 {
+                BLACK
+            }
+            else {
+                light.transmittance_at(&comps.over_point, scene, comps.time).clamp_range(scene.settings.shadow_floor, 1.0) * falloff
+            };
             color = color
                 + lighting(
                     &comps.material,
@@ -40,7 +99,7 @@ impl Scene {
                     &comps.over_point,
                     &comps.e_vec,
                     &comps.n_vec,
-                    light_intensity,
+                    &light_intensity,
                     &comps.parent_inverses,
                 );
         }
@@ -58,7 +117,8 @@ impl Scene {
         color + reflected + refracted + environment
     }
 
-    //Computes the color at a given point
+    //Computes the color seen by a ray
+    //Rays which miss every object see the background
     pub fn compute_color(
         ray: Ray,
         scene: &Scene,
@@ -72,7 +132,7 @@ impl Scene {
             let color = Scene::scene_lighting(scene, &comps, remaining);
             Some(color)
         } else {
-            None
+            Some(scene.background.color_at(&ray.direction))
         }
     }
 
@@ -105,20 +165,22 @@ impl Scene {
                     Material::default(),
                 )),
             ],
+            settings: RenderSettings::default(),
+            background: Background::default(),
         };
         scene
     }
 
     //Gets the color without any lighting calculations
     pub fn compute_color_quick(ray: Ray, scene: &Scene) -> Option<Color> {
-        let intersections = Ray::intersect_scene(scene, ray);
+        let intersections = Ray::intersect_scene(scene, ray.clone());
         let hit = Intersection::hit(&intersections);
         if !hit.is_none() {
             let unwrapped = hit.unwrap();
             let color = &unwrapped.object.get_material().color;
             Some(color.clone())
         } else {
-            None
+            Some(scene.background.color_at(&ray.direction))
         }
     }
 }
@@ -0,0 +1,790 @@
+use crate::core::canvas::Canvas;
+use crate::core::color::Color;
+use crate::core::matrix::Matrix4x4;
+use crate::core::vector::Vec4;
+use crate::materials::material::Material;
+use crate::materials::patterns::*;
+use crate::misc::axis::Axis;
+use crate::misc::yaml::*;
+use crate::objects::csg::*;
+use crate::objects::cone::Cone;
+use crate::objects::cube::Cube;
+use crate::objects::cylinder::Cylinder;
+use crate::objects::group::*;
+use crate::objects::motion::*;
+use crate::objects::object::*;
+use crate::objects::parser::Parser;
+use crate::objects::plane::Plane;
+use crate::objects::smooth_triangle::SmoothTriangle;
+use crate::objects::sphere::Sphere;
+use crate::objects::triangle::Triangle;
+use crate::world::animation::*;
+use crate::world::attenuation::Attenuation;
+use crate::world::background::Background;
+use crate::world::camera::*;
+use crate::world::lighting::*;
+use crate::world::scene::Scene;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+//Keys every shape accepts
+const SHAPE_KEYS: [&str; 4] = ["add", "transform", "material", "shadow"];
+
+//Loads scenes written in the YAML style used by The Ray Tracer Challenge
+//A scene file is a list of items which either add something to the scene or define a reusable value:
+//
+//- add: camera
+//  width: 100
+//  height: 100
+//  field-of-view: 0.785
+//  from: [0, 1.5, -5]
+//  to: [0, 1, 0]
+//  up: [0, 1, 0]
+//
+//- define: red
+//  value:
+//    color: [1, 0, 0]
+//    specular: 0.2
+//
+//- add: sphere
+//  material: red
+//  transform:
+//    - [scale, 0.5, 0.5, 0.5]
+//    - [translate, 0, 1, 0]
+//
+//Angles are in radians, transforms are applied in the order they are listed
+//and the material of a group or csg is used by every object inside it
+//
+//Any number or list of numbers can be animated by giving keyframes instead, which are read at the frame being loaded:
+//
+//  from:
+//    interpolation: ease
+//    keyframes:
+//      - [0, [0, 1.5, -5]]
+//      - { frame: 24, value: [5, 1.5, 0], interpolation: [bezier, 0.4, 0, 0.2, 1] }
+//
+//A keyframe's interpolation (linear, ease or a bezier curve) is used on the way to the next keyframe
+pub struct SceneFile {
+    defines: HashMap<String, Node>,
+    directory: PathBuf,
+    frame: f32,
+}
+
+impl SceneFile {
+    //Reads a scene file from disk
+    pub fn load(path: &str) -> Result<(Scene, Camera), ParseError> {
+        SceneFile::load_frame(path, 0.0)
+    }
+
+    //Reads a scene file from disk as it is at a frame of its animation
+    pub fn load_frame(path: &str, frame: f32) -> Result<(Scene, Camera), ParseError> {
+        let text = fs::read_to_string(path).map_err(|error| ParseError::new(0, &format!("failed to read {}: {}", path, error)))?;
+        let directory = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+        SceneFile::parse_frame(&text, directory, frame)
+    }
+
+    //Builds a scene and camera from the text of a scene file
+    //OBJ files are found relative to the given directory
+    pub fn parse(text: &str, directory: &Path) -> Result<(Scene, Camera), ParseError> {
+        SceneFile::parse_frame(text, directory, 0.0)
+    }
+
+    //Builds a scene and camera from the text of a scene file as it is at a frame of its animation
+    pub fn parse_frame(text: &str, directory: &Path, frame: f32) -> Result<(Scene, Camera), ParseError> {
+        let mut file = SceneFile {
+            defines: HashMap::new(),
+            directory: directory.to_path_buf(),
+            frame,
+        };
+        let document = file.resolve(&Node::parse(text)?)?;
+        let mut scene = Scene::new();
+        let mut camera = None;
+        for item in document.as_list()? {
+            if item.get("define").is_some() {
+                file.define(item)?;
+            }
+            else if let Some(kind) = item.get("add") {
+                match kind.as_str()? {
+                    "camera" => camera = Some(file.camera(item)?),
+                    "light" => {
+                        let visible = match item.get("visible") {
+                            Some(visible) => visible.as_bool()?,
+                            None => false,
+                        };
+                        scene.add_light(file.light(item)?, visible)
+                    }
+                    "background" => scene.background = file.background(item)?,
+                    _ => scene.objects.push(file.object(item)?),
+                }
+            }
+            else {
+                return Err(ParseError::new(item.line, "expected an item starting with 'add' or 'define'"));
+            }
+        }
+        let camera = match camera {
+            Some(camera) => camera,
+            None => return Err(ParseError::new(document.line, "the scene does not add a camera")),
+        };
+        scene.divide(BVH_THRESHOLD);
+        Ok((scene, camera))
+    }
+
+    //Replaces every animated value with its value at the frame being loaded
+    fn resolve(&self, node: &Node) -> Result<Node, ParseError> {
+        let value = match &node.value {
+            Value::Map(_) if node.get("keyframes").is_some() => return self.animated(node),
+            Value::Map(entries) => {
+                let mut resolved = vec![];
+                for (key, entry) in entries {
+                    resolved.push((key.clone(), self.resolve(entry)?));
+                }
+                Value::Map(resolved)
+            }
+            Value::List(items) => Value::List(items.iter().map(|item| self.resolve(item)).collect::<Result<_, _>>()?),
+            Value::Scalar(_) => return Ok(node.clone()),
+        };
+        Ok(Node::new(value, node.line))
+    }
+
+    //Reads the keyframes of an animated value and finds its value at the frame being loaded
+    //Keyframes are either a [frame, value] pair or a map with a "frame", "value" and optional "interpolation"
+    fn animated(&self, node: &Node) -> Result<Node, ParseError> {
+        SceneFile::check_keys(node, &["keyframes", "interpolation"])?;
+        let default = match node.get("interpolation") {
+            Some(interpolation) => SceneFile::interpolation(interpolation)?,
+            None => Interpolation::Linear,
+        };
+        let mut keyframes = vec![];
+        let mut is_list = false;
+        for keyframe in SceneFile::required(node, "keyframes")?.as_list()? {
+            let (frame, value, interpolation) = match &keyframe.value {
+                Value::List(pair) if pair.len() == 2 => (&pair[0], &pair[1], default),
+                Value::Map(_) => {
+                    SceneFile::check_keys(keyframe, &["frame", "value", "interpolation"])?;
+                    let interpolation = match keyframe.get("interpolation") {
+                        Some(interpolation) => SceneFile::interpolation(interpolation)?,
+                        None => default,
+                    };
+                    (SceneFile::required(keyframe, "frame")?, SceneFile::required(keyframe, "value")?, interpolation)
+                }
+                _ => return Err(ParseError::new(keyframe.line, "expected a [frame, value] pair or a map with a frame and value")),
+            };
+            let (line, frame) = (frame.line, frame.as_f32()?);
+            let numbers = match &value.value {
+                Value::List(items) => {
+                    is_list = true;
+                    items.iter().map(|item| item.as_f32()).collect::<Result<Vec<f32>, _>>()?
+                }
+                _ => vec![value.as_f32()?],
+            };
+            if keyframes.first().is_some_and(|first: &Keyframe| first.value.len() != numbers.len()) {
+                return Err(ParseError::new(value.line, "every keyframe must have the same number of values"));
+            }
+            if keyframes.iter().any(|other: &Keyframe| other.frame == frame) {
+                return Err(ParseError::new(line, "two keyframes can not be on the same frame"));
+            }
+            keyframes.push(Keyframe::new(frame, numbers, interpolation));
+        }
+        if keyframes.is_empty() {
+            return Err(ParseError::new(node.line, "an animated value needs at least one keyframe"));
+        }
+        let numbers = Track::new(keyframes).value_at(self.frame);
+        let scalar = |number: &f32| Node::new(Value::Scalar(number.to_string()), node.line);
+        if is_list {
+            Ok(Node::new(Value::List(numbers.iter().map(scalar).collect()), node.line))
+        }
+        else {
+            Ok(scalar(&numbers[0]))
+        }
+    }
+
+    //Reads how an animated value changes between keyframes
+    fn interpolation(node: &Node) -> Result<Interpolation, ParseError> {
+        if let Value::List(items) = &node.value {
+            if items.len() != 5 || items[0].as_str()? != "bezier" {
+                return Err(ParseError::new(node.line, "a curve is given as [bezier, x1, y1, x2, y2]"));
+            }
+            let (x1, y1, x2, y2) = (items[1].as_f32()?, items[2].as_f32()?, items[3].as_f32()?, items[4].as_f32()?);
+            if !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2) {
+                return Err(ParseError::new(node.line, "the x coordinates of a bezier curve must be between 0 and 1"));
+            }
+            return Ok(Interpolation::Bezier(x1, y1, x2, y2));
+        }
+        match node.as_str()? {
+            "linear" => Ok(Interpolation::Linear),
+            "ease" => Ok(Interpolation::Ease),
+            other => Err(ParseError::new(node.line, &format!("unknown interpolation '{}'", other))),
+        }
+    }
+
+    //Stores a named value, merging it over the value it extends
+    fn define(&mut self, node: &Node) -> Result<(), ParseError> {
+        SceneFile::check_keys(node, &["define", "value", "extend"])?;
+        let name = node.get("define").unwrap().as_str()?.to_string();
+        let mut value = SceneFile::required(node, "value")?.clone();
+        if let Some(extend) = node.get("extend") {
+            let base = self.lookup(extend)?;
+            let mut entries = base.as_map()?.clone();
+            for (key, entry) in value.as_map()? {
+                entries.retain(|(existing, _)| existing != key);
+                entries.push((key.clone(), entry.clone()));
+            }
+            value = Node::new(Value::Map(entries), value.line);
+        }
+        self.defines.insert(name, value);
+        Ok(())
+    }
+
+    //Finds a defined value from its name
+    fn lookup(&self, name: &Node) -> Result<&Node, ParseError> {
+        let text = name.as_str()?;
+        match self.defines.get(text) {
+            Some(node) => Ok(node),
+            None => Err(ParseError::new(name.line, &format!("'{}' has not been defined", text))),
+        }
+    }
+
+    //Gets a key which must be present
+    fn required<'a>(node: &'a Node, key: &str) -> Result<&'a Node, ParseError> {
+        match node.get(key) {
+            Some(value) => Ok(value),
+            None => Err(ParseError::new(node.line, &format!("missing '{}'", key))),
+        }
+    }
+
+    //Rejects keys which are not understood so typos do not go unnoticed
+    fn check_keys(node: &Node, allowed: &[&str]) -> Result<(), ParseError> {
+        for (key, value) in node.as_map()? {
+            if !allowed.contains(&key.as_str()) {
+                return Err(ParseError::new(value.line, &format!("unknown key '{}'", key)));
+            }
+        }
+        Ok(())
+    }
+
+    //Adds the keys specific to one shape to the keys every shape accepts
+    fn shape_keys<'a>(extra: &[&'a str]) -> Vec<&'a str> {
+        let mut keys = SHAPE_KEYS.to_vec();
+        keys.extend_from_slice(extra);
+        keys
+    }
+
+    //Reads a point from a list of three numbers
+    fn point(node: &Node) -> Result<Vec4, ParseError> {
+        let (x, y, z) = node.as_triple()?;
+        Ok(Vec4::new(x, y, z, 1.0))
+    }
+
+    //Reads a vector from a list of three numbers
+    fn vector(node: &Node) -> Result<Vec4, ParseError> {
+        let (x, y, z) = node.as_triple()?;
+        Ok(Vec4::new(x, y, z, 0.0))
+    }
+
+    //Reads a vector which can be normalized from a list of three numbers
+    fn direction(node: &Node) -> Result<Vec4, ParseError> {
+        let vector = SceneFile::vector(node)?;
+        if Vec4::magnitude(&vector) == 0.0 {
+            return Err(ParseError::new(node.line, "a direction can not be a zero vector"));
+        }
+        Ok(vector)
+    }
+
+    //Reads a color from a list of three numbers
+    fn color(node: &Node) -> Result<Color, ParseError> {
+        let (r, g, b) = node.as_triple()?;
+        Ok(Color::new(r, g, b))
+    }
+
+    //Creates the camera
+    //The "projection" is perspective (given "field-of-view"), orthographic (given "view-width" and optionally
+    //"view-height", which otherwise follows the aspect ratio), equirectangular or fisheye (given "field-of-view")
+    //A camera with a "shutter-open" and "shutter-close" sends rays at times between them, moving from its start view
+    //to the view given by "end-from", "end-to" and "end-up" (which defaults to "up")
+    //Giving an "aperture" radius blurs everything away from the "focal-distance", and "aperture-blades" gives the
+    //lens a polygonal shape instead of a disk
+    //The focal distance defaults to the distance from "from" to "to", and is also where the eyes of stereo renders converge
+    fn camera(&self, node: &Node) -> Result<Camera, ParseError> {
+        SceneFile::check_keys(
+            node,
+            &[
+                "add", "width", "height", "projection", "field-of-view", "view-width", "view-height", "from", "to", "up",
+                "aperture", "focal-distance", "aperture-blades", "shutter-open", "shutter-close", "end-from", "end-to", "end-up",
+            ],
+        )?;
+        let width = SceneFile::required(node, "width")?.as_usize()?;
+        let height = SceneFile::required(node, "height")?.as_usize()?;
+        if width == 0 || height == 0 {
+            return Err(ParseError::new(node.line, "the camera width and height must be above 0"));
+        }
+        let projection = match node.get("projection") {
+            Some(projection) => projection.as_str()?,
+            None => "perspective",
+        };
+        let mut camera = match projection {
+            "perspective" => Camera::new(width, height, SceneFile::required(node, "field-of-view")?.as_f32()?.to_degrees()),
+            "orthographic" => {
+                let view_width = SceneFile::required(node, "view-width")?.as_f32()?;
+                let view_height = match node.get("view-height") {
+                    Some(view_height) => view_height.as_f32()?,
+                    None => view_width * height as f32 / width as f32,
+                };
+                if view_width <= 0.0 || view_height <= 0.0 {
+                    return Err(ParseError::new(node.line, "the view width and height must be above 0"));
+                }
+                Camera::orthographic(width, height, view_width, view_height)
+            }
+            "equirectangular" => Camera::equirectangular(width, height),
+            "fisheye" => Camera::fisheye(width, height, SceneFile::required(node, "field-of-view")?.as_f32()?.to_degrees()),
+            other => return Err(ParseError::new(node.get("projection").unwrap().line, &format!("unknown projection '{}'", other))),
+        };
+        let from = SceneFile::point(SceneFile::required(node, "from")?)?;
+        let to = SceneFile::point(SceneFile::required(node, "to")?)?;
+        let up = SceneFile::vector(SceneFile::required(node, "up")?)?;
+        let target_distance = Vec4::magnitude(&(&to - &from));
+        camera.transform(Matrix4x4::view_transform(from, to, up.clone()));
+        if let Some(aperture) = node.get("aperture") {
+            camera.aperture = aperture.as_f32()?;
+            if camera.aperture < 0.0 {
+                return Err(ParseError::new(aperture.line, "the aperture must not be negative"));
+            }
+        }
+        camera.focal_distance = match node.get("focal-distance") {
+            Some(distance) => distance.as_f32()?,
+            None => target_distance,
+        };
+        if camera.focal_distance <= 0.0 {
+            return Err(ParseError::new(node.line, "the focal distance must be above 0"));
+        }
+        if let Some(blades) = node.get("aperture-blades") {
+            let sides = blades.as_usize()?;
+            if sides < 3 {
+                return Err(ParseError::new(blades.line, "an aperture needs at least 3 blades"));
+            }
+            camera.aperture_shape = ApertureShape::Polygon(sides);
+        }
+        if let Some(open) = node.get("shutter-open") {
+            camera.shutter_open = open.as_f32()?;
+        }
+        if let Some(close) = node.get("shutter-close") {
+            camera.shutter_close = close.as_f32()?;
+        }
+        if !(0.0..=1.0).contains(&camera.shutter_open) || !(0.0..=1.0).contains(&camera.shutter_close) {
+            return Err(ParseError::new(node.line, "the shutter must open and close between 0 and 1"));
+        }
+        if node.get("end-from").is_some() || node.get("end-to").is_some() || node.get("end-up").is_some() {
+            let end_from = SceneFile::point(SceneFile::required(node, "end-from")?)?;
+            let end_to = SceneFile::point(SceneFile::required(node, "end-to")?)?;
+            let end_up = match node.get("end-up") {
+                Some(end_up) => SceneFile::vector(end_up)?,
+                None => up,
+            };
+            camera.motion = Some(Motion::new(Matrix4x4::view_transform(end_from, end_to, end_up)));
+        }
+        Ok(camera)
+    }
+
+    //Creates a point light (given "at"), an area light (given "corner"), a sphere light (given "center" and "radius"),
+    //a disk light (given "center", "radius" and "normal"), a spot light (given "at" and "direction")
+    //or a directional light (given only "direction")
+    //Every light except directional lights can be given an attenuation, and area, sphere and disk lights a sampling
+    //and be made visible
+    fn light(&self, node: &Node) -> Result<Box<dyn Light>, ParseError> {
+        let intensity = SceneFile::color(SceneFile::required(node, "intensity")?)?;
+        let attenuation = match node.get("attenuation") {
+            Some(attenuation) => SceneFile::attenuation(attenuation)?,
+            None => Attenuation::default(),
+        };
+        let sampling = match node.get("sampling") {
+            Some(sampling) => SceneFile::sampling(sampling)?,
+            None => LightSampling::Jittered,
+        };
+        if node.get("corner").is_some() {
+            SceneFile::check_keys(node, &["add", "corner", "uvec", "usteps", "vvec", "vsteps", "intensity", "attenuation", "sampling", "visible"])?;
+            let corner = SceneFile::point(SceneFile::required(node, "corner")?)?;
+            let uvec = SceneFile::vector(SceneFile::required(node, "uvec")?)?;
+            let vvec = SceneFile::vector(SceneFile::required(node, "vvec")?)?;
+            let (usteps, vsteps) = SceneFile::steps(node)?;
+            let mut light = AreaLight::new(corner, uvec, usteps as i32, vvec, vsteps as i32, intensity);
+            light.attenuation = attenuation;
+            light.sampling = sampling;
+            Ok(Box::new(light))
+        }
+        else if node.get("center").is_some() {
+            let center = SceneFile::point(SceneFile::required(node, "center")?)?;
+            let radius = SceneFile::required(node, "radius")?.as_f32()?;
+            if radius <= 0.0 {
+                return Err(ParseError::new(node.line, "the radius of a light must be above 0"));
+            }
+            let (usteps, vsteps) = SceneFile::steps(node)?;
+            if let Some(normal) = node.get("normal") {
+                SceneFile::check_keys(node, &["add", "center", "normal", "radius", "usteps", "vsteps", "intensity", "attenuation", "sampling", "visible"])?;
+                let mut light = DiskLight::new(center, SceneFile::direction(normal)?, radius, usteps, vsteps, intensity);
+                light.attenuation = attenuation;
+                light.sampling = sampling;
+                Ok(Box::new(light))
+            }
+            else {
+                SceneFile::check_keys(node, &["add", "center", "radius", "usteps", "vsteps", "intensity", "attenuation", "sampling", "visible"])?;
+                let mut light = SphereLight::new(center, radius, usteps, vsteps, intensity);
+                light.attenuation = attenuation;
+                light.sampling = sampling;
+                Ok(Box::new(light))
+            }
+        }
+        else if node.get("at").is_some() && node.get("direction").is_some() {
+            SceneFile::check_keys(node, &["add", "at", "direction", "inner-angle", "outer-angle", "intensity", "attenuation"])?;
+            let position = SceneFile::point(SceneFile::required(node, "at")?)?;
+            let direction = SceneFile::direction(SceneFile::required(node, "direction")?)?;
+            let inner_angle = SceneFile::required(node, "inner-angle")?.as_f32()?;
+            let outer_angle = SceneFile::required(node, "outer-angle")?.as_f32()?;
+            if inner_angle < 0.0 || inner_angle > outer_angle {
+                return Err(ParseError::new(node.line, "spot light angles must be positive with the inner angle no larger than the outer angle"));
+            }
+            let mut light = SpotLight::new(intensity, position, direction, inner_angle.to_degrees(), outer_angle.to_degrees());
+            light.attenuation = attenuation;
+            Ok(Box::new(light))
+        }
+        else if node.get("direction").is_some() {
+            SceneFile::check_keys(node, &["add", "direction", "intensity"])?;
+            let direction = SceneFile::direction(SceneFile::required(node, "direction")?)?;
+            Ok(Box::new(DirectionalLight::new(intensity, direction)))
+        }
+        else {
+            SceneFile::check_keys(node, &["add", "at", "intensity", "attenuation"])?;
+            let position = SceneFile::point(SceneFile::required(node, "at")?)?;
+            let mut light = PointLight::new(intensity, position);
+            light.attenuation = attenuation;
+            Ok(Box::new(light))
+        }
+    }
+
+    //Reads the number of cells along each edge of an area light
+    fn steps(node: &Node) -> Result<(usize, usize), ParseError> {
+        let usteps = SceneFile::required(node, "usteps")?.as_usize()?;
+        let vsteps = SceneFile::required(node, "vsteps")?.as_usize()?;
+        if usteps == 0 || vsteps == 0 {
+            return Err(ParseError::new(node.line, "area lights need at least one step along each edge"));
+        }
+        Ok((usteps, vsteps))
+    }
+
+    //Reads how points on an area light are sampled
+    fn sampling(node: &Node) -> Result<LightSampling, ParseError> {
+        match node.as_str()? {
+            "stratified" => Ok(LightSampling::Stratified),
+            "jittered" => Ok(LightSampling::Jittered),
+            "low-discrepancy" => Ok(LightSampling::LowDiscrepancy),
+            other => Err(ParseError::new(node.line, &format!("unknown light sampling '{}'", other))),
+        }
+    }
+
+    //Reads an attenuation from its name or a list of [constant, linear, quadratic] coefficients
+    fn attenuation(node: &Node) -> Result<Attenuation, ParseError> {
+        if let Value::List(_) = node.value {
+            let (constant, linear, quadratic) = node.as_triple()?;
+            if constant < 0.0 || linear < 0.0 || quadratic < 0.0 || constant + linear + quadratic == 0.0 {
+                return Err(ParseError::new(node.line, "attenuation coefficients must be positive and not all zero"));
+            }
+            return Ok(Attenuation::Coefficients { constant, linear, quadratic });
+        }
+        match node.as_str()? {
+            "none" => Ok(Attenuation::None),
+            "linear" => Ok(Attenuation::Linear),
+            "inverse-square" => Ok(Attenuation::InverseSquare),
+            other => Err(ParseError::new(node.line, &format!("unknown attenuation '{}'", other))),
+        }
+    }
+
+    //Creates a solid color (given "color"), a gradient (given "top" and "bottom") or an environment image (given "file") background
+    fn background(&self, node: &Node) -> Result<Background, ParseError> {
+        SceneFile::check_keys(node, &["add", "color", "top", "bottom", "file"])?;
+        if let Some(file) = node.get("file") {
+            Ok(Background::Environment(self.image(file)?))
+        }
+        else if let Some(color) = node.get("color") {
+            Ok(Background::Color(SceneFile::color(color)?))
+        }
+        else {
+            Ok(Background::Gradient {
+                top: SceneFile::color(SceneFile::required(node, "top")?)?,
+                bottom: SceneFile::color(SceneFile::required(node, "bottom")?)?,
+            })
+        }
+    }
+
+    //Combines a list of transforms, where each item is either [name, values...] or the name of a defined list
+    fn transform(&self, node: Option<&Node>) -> Result<Matrix4x4, ParseError> {
+        let mut result = Matrix4x4::identity();
+        let node = match node {
+            Some(node) => node,
+            None => return Ok(result),
+        };
+        for item in node.as_list()? {
+            if let Value::Scalar(_) = item.value {
+                let defined = self.lookup(item)?;
+                result = self.transform(Some(defined))? * result;
+                continue;
+            }
+            let values = item.as_list()?;
+            if values.is_empty() {
+                return Err(ParseError::new(item.line, "empty transform"));
+            }
+            let name = values[0].as_str()?;
+            let mut numbers = vec![];
+            for value in &values[1..] {
+                numbers.push(value.as_f32()?);
+            }
+            let expected = match name {
+                "translate" | "scale" => 3,
+                "rotate-x" | "rotate-y" | "rotate-z" => 1,
+                "shear" => 6,
+                _ => return Err(ParseError::new(item.line, &format!("unknown transform '{}'", name))),
+            };
+            if numbers.len() != expected {
+                return Err(ParseError::new(item.line, &format!("'{}' takes {} numbers but was given {}", name, expected, numbers.len())));
+            }
+            let matrix = match name {
+                "translate" => Matrix4x4::translation(numbers[0], numbers[1], numbers[2]),
+                "scale" => Matrix4x4::scaling(numbers[0], numbers[1], numbers[2]),
+                "rotate-x" => Matrix4x4::rotation(Axis::X, numbers[0].to_degrees()),
+                "rotate-y" => Matrix4x4::rotation(Axis::Y, numbers[0].to_degrees()),
+                "rotate-z" => Matrix4x4::rotation(Axis::Z, numbers[0].to_degrees()),
+                _ => Matrix4x4::shearing(numbers[0], numbers[1], numbers[2], numbers[3], numbers[4], numbers[5]),
+            };
+            result = matrix * result;
+        }
+        if result.inverse().is_none() {
+            return Err(ParseError::new(node.line, "the transform cannot be inverted"));
+        }
+        Ok(result)
+    }
+
+    //Creates a material from a map of properties or the name of a defined material
+    fn material(&self, node: Option<&Node>) -> Result<Material, ParseError> {
+        let mut material = Material::default();
+        let node = match node {
+            Some(node) => node,
+            None => return Ok(material),
+        };
+        let node = match node.value {
+            Value::Scalar(_) => self.lookup(node)?,
+            _ => node,
+        };
+        for (key, value) in node.as_map()? {
+            match key.as_str() {
+                "color" => material.color = SceneFile::color(value)?,
+                "ambient" => material.ambient = value.as_f32()?,
+                "diffuse" => material.diffuse = value.as_f32()?,
+                "specular" => material.specular = value.as_f32()?,
+                "shininess" => material.shininess = value.as_f32()?,
+                "reflective" => material.reflectivity = value.as_f32()?,
+                "transparency" => material.transparency = value.as_f32()?,
+                "refractive-index" => material.refractive_index = value.as_f32()?,
+                "environment-lighting" => material.environment_lighting = value.as_f32()?,
+                "shadow" => material.casts_shadows = value.as_bool()?,
+                "emission" => material.emission = SceneFile::color(value)?,
+                "pattern" => material.pattern = Some(self.pattern(value)?),
+                _ => return Err(ParseError::new(value.line, &format!("unknown material property '{}'", key))),
+            }
+        }
+        Ok(material)
+    }
+
+    //Creates a pattern from its type, two colors and an optional transform
+    //Texture patterns take an image file, a mapping and a filter instead of colors
+    fn pattern(&self, node: &Node) -> Result<Box<dyn Pattern>, ParseError> {
+        let kind = SceneFile::required(node, "type")?;
+        let transform = self.transform(node.get("transform"))?;
+        if kind.as_str()? == "texture" {
+            return self.texture(node, transform);
+        }
+        SceneFile::check_keys(node, &["type", "colors", "transform"])?;
+        if kind.as_str()? == "test" {
+            return Ok(Box::new(TestPattern::new(transform)));
+        }
+        let colors = SceneFile::required(node, "colors")?;
+        let list = colors.as_list()?;
+        if list.len() != 2 {
+            return Err(ParseError::new(colors.line, "patterns take exactly 2 colors"));
+        }
+        let color1 = SceneFile::color(&list[0])?;
+        let color2 = SceneFile::color(&list[1])?;
+        match kind.as_str()? {
+            "stripes" => Ok(Box::new(StripePattern::new(color1, color2, transform))),
+            "gradient" => Ok(Box::new(GradientPattern::new(color1, color2, transform))),
+            "rings" => Ok(Box::new(RingPattern::new(color1, color2, transform))),
+            "checkers" => Ok(Box::new(CheckerboardPattern::new(color1, color2, transform))),
+            other => Err(ParseError::new(kind.line, &format!("unknown pattern '{}'", other))),
+        }
+    }
+
+    //Creates a texture pattern from an image file relative to the scene
+    fn texture(&self, node: &Node, transform: Matrix4x4) -> Result<Box<dyn Pattern>, ParseError> {
+        SceneFile::check_keys(node, &["type", "file", "mapping", "filter", "transform"])?;
+        let canvas = self.image(SceneFile::required(node, "file")?)?;
+        let mapping = match node.get("mapping") {
+            Some(mapping) => match mapping.as_str()? {
+                "spherical" => UvMapping::Spherical,
+                "planar" => UvMapping::Planar,
+                "cylindrical" => UvMapping::Cylindrical,
+                "cube" => UvMapping::Cube,
+                other => return Err(ParseError::new(mapping.line, &format!("unknown mapping '{}'", other))),
+            },
+            None => UvMapping::Spherical,
+        };
+        let filter = match node.get("filter") {
+            Some(filter) => match filter.as_str()? {
+                "nearest" => TextureFilter::Nearest,
+                "bilinear" => TextureFilter::Bilinear,
+                other => return Err(ParseError::new(filter.line, &format!("unknown filter '{}'", other))),
+            },
+            None => TextureFilter::Bilinear,
+        };
+        Ok(Box::new(TexturePattern::new(canvas, mapping, filter, transform)))
+    }
+
+    //Reads an image file relative to the scene
+    fn image(&self, file_node: &Node) -> Result<Arc<Canvas>, ParseError> {
+        let path = self.directory.join(file_node.as_str()?);
+        match Canvas::read_file(&path.to_string_lossy()) {
+            Ok(canvas) => Ok(Arc::new(canvas)),
+            Err(error) => Err(ParseError::new(file_node.line, &format!("failed to read {}: {}", path.display(), error))),
+        }
+    }
+
+    //Creates any shape, group, csg or obj file
+    fn object(&self, node: &Node) -> Result<Box<dyn Object>, ParseError> {
+        let kind = SceneFile::required(node, "add")?;
+        let transform = self.transform(node.get("transform"))?;
+        let mut material = self.material(node.get("material"))?;
+        if let Some(shadow) = node.get("shadow") {
+            material.casts_shadows = shadow.as_bool()?;
+        }
+        //Spheres, cubes and groups given an "end-transform" move to it over the shutter interval
+        //Both transforms must be made of moves, rotations and scales so the object keeps its shape as it turns
+        let motion = match node.get("end-transform") {
+            Some(end) => {
+                let end = self.transform(Some(end))?;
+                if Pose::from_matrix(&transform).is_none() || Pose::from_matrix(&end).is_none() {
+                    return Err(ParseError::new(node.line, "a moving object can not be sheared"));
+                }
+                let motion = Motion::new(end);
+                if !motion.is_invertible(&transform) {
+                    return Err(ParseError::new(node.line, "a moving object can not be mirrored at only one end of its motion"));
+                }
+                Some(motion)
+            }
+            None => None,
+        };
+        let object: Box<dyn Object> = match kind.as_str()? {
+            "sphere" => {
+                SceneFile::check_keys(node, &SceneFile::shape_keys(&["end-transform"]))?;
+                let mut sphere = Sphere::new(transform, material);
+                sphere.motion = motion;
+                Box::new(sphere)
+            }
+            "plane" => {
+                SceneFile::check_keys(node, &SHAPE_KEYS)?;
+                Box::new(Plane::new(transform, material))
+            }
+            "cube" => {
+                SceneFile::check_keys(node, &SceneFile::shape_keys(&["end-transform"]))?;
+                let mut cube = Cube::new(transform, material);
+                cube.motion = motion;
+                Box::new(cube)
+            }
+            "cylinder" | "cone" => {
+                SceneFile::check_keys(node, &SceneFile::shape_keys(&["min", "max", "closed"]))?;
+                let minimum = match node.get("min") {
+                    Some(value) => value.as_f32()?,
+                    None => -f32::INFINITY,
+                };
+                let maximum = match node.get("max") {
+                    Some(value) => value.as_f32()?,
+                    None => f32::INFINITY,
+                };
+                let capped = match node.get("closed") {
+                    Some(value) => value.as_bool()?,
+                    None => false,
+                };
+                if kind.as_str()? == "cylinder" {
+                    Box::new(Cylinder::new(transform, material, minimum, maximum, capped))
+                }
+                else {
+                    Box::new(Cone::new(transform, material, minimum, maximum, capped))
+                }
+            }
+            "triangle" => {
+                //Triangles have no transform of their own so it is applied to their points
+                SceneFile::check_keys(node, &SceneFile::shape_keys(&["p1", "p2", "p3"]))?;
+                let p1 = &transform * SceneFile::point(SceneFile::required(node, "p1")?)?;
+                let p2 = &transform * SceneFile::point(SceneFile::required(node, "p2")?)?;
+                let p3 = &transform * SceneFile::point(SceneFile::required(node, "p3")?)?;
+                Box::new(Triangle::new(p1, p2, p3, material))
+            }
+            "smooth-triangle" => {
+                SceneFile::check_keys(node, &SceneFile::shape_keys(&["p1", "p2", "p3", "n1", "n2", "n3"]))?;
+                let normal_transform = transform.inverse().unwrap().transpose();
+                let p1 = &transform * SceneFile::point(SceneFile::required(node, "p1")?)?;
+                let p2 = &transform * SceneFile::point(SceneFile::required(node, "p2")?)?;
+                let p3 = &transform * SceneFile::point(SceneFile::required(node, "p3")?)?;
+                let n1 = (&normal_transform * SceneFile::vector(SceneFile::required(node, "n1")?)?).normalize();
+                let n2 = (&normal_transform * SceneFile::vector(SceneFile::required(node, "n2")?)?).normalize();
+                let n3 = (&normal_transform * SceneFile::vector(SceneFile::required(node, "n3")?)?).normalize();
+                Box::new(SmoothTriangle::new(p1, p2, p3, n1, n2, n3, material))
+            }
+            "group" => {
+                SceneFile::check_keys(node, &SceneFile::shape_keys(&["children", "end-transform"]))?;
+                //Children only take on the group's shadow setting through its material, so one is needed
+                if let (Some(shadow), None) = (node.get("shadow"), node.get("material")) {
+                    return Err(ParseError::new(shadow.line, "a group needs a material to set whether its children cast shadows"));
+                }
+                let mut group = Group::new(transform, material);
+                group.motion = motion;
+                if let Some(children) = node.get("children") {
+                    for child in children.as_list()? {
+                        let mut object = self.object(child)?;
+                        object.push_parent_inverse(group.get_inverse().clone());
+                        //Children keep their own material unless the group is given one
+                        if node.get("material").is_some() {
+                            object.set_parent_material(&group.material);
+                        }
+                        group.push(object);
+                    }
+                }
+                Box::new(group)
+            }
+            "csg" => {
+                SceneFile::check_keys(node, &SceneFile::shape_keys(&["operation", "left", "right"]))?;
+                let operation_node = SceneFile::required(node, "operation")?;
+                let operation = match operation_node.as_str()? {
+                    "union" => Operation::Union,
+                    "intersection" | "intersect" => Operation::Intersect,
+                    "difference" => Operation::Difference,
+                    other => return Err(ParseError::new(operation_node.line, &format!("unknown csg operation '{}'", other))),
+                };
+                let left = self.object(SceneFile::required(node, "left")?)?;
+                let right = self.object(SceneFile::required(node, "right")?)?;
+                Box::new(CSG::new(transform, material, left, right, operation))
+            }
+            "obj" => {
+                SceneFile::check_keys(node, &SceneFile::shape_keys(&["file", "end-transform"]))?;
+                let file_node = SceneFile::required(node, "file")?;
+                let path = self.directory.join(file_node.as_str()?);
+                //Errors inside the obj file point to the obj entry, with the line of the obj file in the message
+                let parser = File::open(&path).and_then(Parser::parse_obj)
+                    .map_err(|error| ParseError::new(node.line, &format!("failed to read {}: {}", path.display(), error)))?;
+                let mut group = Group::new(transform, material);
+                parser.convert_to_group(&mut group);
+                group.motion = motion;
+                Box::new(group)
+            }
+            other => return Err(ParseError::new(kind.line, &format!("unknown object '{}'", other))),
+        };
+        Ok(object)
+    }
+}
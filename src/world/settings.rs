@@ -0,0 +1,49 @@
+use crate::world::antialiasing::*;
+use std::thread;
+
+//Ways the color seen by a ray is computed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Integrator {
+    //Phong shading with recursive reflection and refraction
+    Whitted,
+    //Monte Carlo path tracing which averages many random light paths through each pixel
+    PathTracer,
+}
+
+//Settings which control how a scene is rendered
+#[derive(Debug, PartialEq, Clone)]
+pub struct RenderSettings {
+    pub max_depth: i32, //Number of times a ray may bounce for reflection, refraction and environment lighting
+    pub samples: usize, //Number of rays sent through each pixel when supersampling or path tracing (grid and jittered samplers round up to a square)
+    pub sampler: Sampler, //How the rays sent through a pixel are spread out
+    pub filter: Filter, //How the colors of the rays sent through a pixel are weighted
+    pub adaptive_threshold: f32, //Largest difference between neighbouring colors before adaptive renders send more rays
+    pub adaptive_depth: usize, //Number of times adaptive renders may split a pixel into quarters
+    pub threads: usize, //Number of threads used when rendering
+    pub show_progress: bool, //Prints the progress of renders
+    pub integrator: Integrator,
+    pub shadow_floor: f32, //Lowest light intensity used in shadows (0.0 gives fully dark shadows)
+    pub transparent_shadows: bool, //Lets light through transparent objects onto the surfaces behind them (false makes every shadow opaque)
+    pub seed: u64, //Starting point for random sampling, renders with the same seed give identical images
+}
+
+impl Default for RenderSettings {
+    //Creates the default RenderSettings
+    fn default() -> RenderSettings {
+        RenderSettings {
+            max_depth: 5,
+            //A 2x2 grid, since grids round up to a square (supersampling used to send 5 rays, through the center and corners)
+            samples: 4,
+            sampler: Sampler::Grid,
+            filter: Filter::Box,
+            adaptive_threshold: 0.1,
+            adaptive_depth: 2,
+            threads: thread::available_parallelism().map_or(1, |count| count.get()),
+            show_progress: true,
+            integrator: Integrator::Whitted,
+            shadow_floor: 0.0,
+            transparent_shadows: true,
+            seed: 0,
+        }
+    }
+}
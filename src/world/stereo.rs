@@ -0,0 +1,118 @@
+use crate::core::canvas::Canvas;
+use crate::core::matrix::Matrix4x4;
+use crate::core::vector::Vec4;
+use crate::objects::motion::Motion;
+use crate::world::camera::Camera;
+
+//Ways the two eyes of a stereo rig are aimed at the convergence distance
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Convergence {
+    //Each eye turns inwards to look at the point where the views meet, which is simple but slightly
+    //distorts the edges of the image vertically
+    ToeIn,
+    //Both eyes look straight ahead and shift their views inwards so they line up at the convergence distance
+    OffAxis,
+}
+
+//Ways the views of both eyes are placed on a single canvas
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Layout {
+    //The left eye's view on the left half and the right eye's view on the right half
+    SideBySide,
+    //The left eye's view on the top half and the right eye's view on the bottom half
+    TopBottom,
+}
+
+//The eyes of a stereo rig
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Eye {
+    Left,
+    Right,
+}
+
+//A StereoRig renders a scene from two eyes either side of a camera
+//Each eye sees the camera's full size view, so the composite canvas is twice as wide or twice as tall as the camera
+#[derive(Debug, Clone)]
+pub struct StereoRig {
+    pub camera: Camera, //Camera in the middle of the eyes
+    pub interocular: f32, //Distance between the eyes
+    pub convergence_distance: f32, //Distance in front of the camera where both eyes see the same point in the same place
+    pub convergence: Convergence,
+    pub layout: Layout,
+}
+
+impl StereoRig {
+    //Creates a new StereoRig with off-axis eyes placed side by side
+    pub fn new(camera: Camera, interocular: f32, convergence_distance: f32) -> StereoRig {
+        StereoRig {
+            camera,
+            interocular,
+            convergence_distance,
+            convergence: Convergence::OffAxis,
+            layout: Layout::SideBySide,
+        }
+    }
+
+    //Finds the width and height of the canvas holding both views
+    pub fn size(&self) -> (usize, usize) {
+        let (width, height) = (self.camera.hsize as usize, self.camera.vsize as usize);
+        match self.layout {
+            Layout::SideBySide => (width * 2, height),
+            Layout::TopBottom => (width, height * 2),
+        }
+    }
+
+    //Creates the camera seen through one eye
+    //The camera's +x axis points to its left, so the left eye sits on the positive side
+    pub fn eye(&self, eye: Eye) -> Camera {
+        let side = match eye {
+            Eye::Left => self.interocular / 2.0,
+            Eye::Right => -self.interocular / 2.0,
+        };
+        let mut camera = self.camera.clone();
+        let offset = match self.convergence {
+            Convergence::ToeIn => Matrix4x4::view_transform(
+                Vec4::new(side, 0.0, 0.0, 1.0),
+                Vec4::new(0.0, 0.0, -self.convergence_distance, 1.0),
+                Vec4::new(0.0, 1.0, 0.0, 0.0),
+            ),
+            Convergence::OffAxis => {
+                camera.shift_x -= side / self.convergence_distance;
+                Matrix4x4::translation(-side, 0.0, 0.0)
+            }
+        };
+        camera.transform = &offset * &self.camera.transform;
+        if let Some(motion) = &self.camera.motion {
+            camera.motion = Some(Motion::new(&offset * &motion.end));
+        }
+        camera
+    }
+
+    //Copies the view seen through one eye onto its half of the composite canvas
+    pub fn place(&self, canvas: &mut Canvas, view: &Canvas, eye: Eye) {
+        let (offset_x, offset_y) = match (eye, self.layout) {
+            (Eye::Left, _) => (0, 0),
+            (Eye::Right, Layout::SideBySide) => (view.width as i32, 0),
+            (Eye::Right, Layout::TopBottom) => (0, view.height as i32),
+        };
+        for y in 0..view.height as i32 {
+            for x in 0..view.width as i32 {
+                canvas.set(view.get(x, y).unwrap().clone(), x + offset_x, y + offset_y);
+            }
+        }
+    }
+
+    //Renders the view from each eye with the given render function and places both on the canvas
+    //The canvas must be the size of the rig
+    pub fn render<F>(rig: &StereoRig, canvas: &mut Canvas, mut render: F)
+    where
+        F: FnMut(&Camera, &mut Canvas),
+    {
+        for eye in [Eye::Left, Eye::Right].iter() {
+            let camera = rig.eye(*eye);
+            let mut view = Canvas::new(camera.hsize as usize, camera.vsize as usize);
+            render(&camera, &mut view);
+            rig.place(canvas, &view, *eye);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::world::animation::*;
+
+    //Tests the curves values follow between keyframes
+    #[test]
+    fn interpolation_curves() {
+        assert_eq!(Interpolation::Linear.amount(0.25), 0.25);
+        assert_eq!(Interpolation::Ease.amount(0.5), 0.5);
+        assert!(Interpolation::Ease.amount(0.1) < 0.1);
+        assert!(Interpolation::Ease.amount(0.9) > 0.9);
+        assert_eq!(Interpolation::Linear.amount(1.5), 1.0);
+
+        let straight = Interpolation::Bezier(0.25, 0.25, 0.75, 0.75);
+        assert!((straight.amount(0.3) - 0.3).abs() < 0.0001);
+        let ease_in = Interpolation::Bezier(0.42, 0.0, 1.0, 1.0);
+        assert!(ease_in.amount(0.0).abs() < 0.0001);
+        assert!(ease_in.amount(0.5) < 0.5);
+        assert!((ease_in.amount(1.0) - 1.0).abs() < 0.0001);
+    }
+
+    //Tests finding values between, before and after keyframes
+    #[test]
+    fn track_values() {
+        let track = Track::new(vec![
+            Keyframe::new(10.0, vec![2.0, 0.0], Interpolation::Ease),
+            Keyframe::new(0.0, vec![0.0, 0.0], Interpolation::Linear),
+            Keyframe::new(20.0, vec![4.0, 10.0], Interpolation::Linear),
+        ]);
+        assert_eq!(track.keyframes[0].frame, 0.0);
+        assert_eq!(track.value_at(-5.0), vec![0.0, 0.0]);
+        assert_eq!(track.value_at(0.0), vec![0.0, 0.0]);
+        assert_eq!(track.value_at(5.0), vec![1.0, 0.0]);
+        assert_eq!(track.value_at(10.0), vec![2.0, 0.0]);
+        assert_eq!(track.value_at(15.0), vec![3.0, 5.0]);
+        assert!(track.value_at(12.0)[1] < 2.0);
+        assert_eq!(track.value_at(25.0), vec![4.0, 10.0]);
+        assert_eq!(Track::new(vec![Keyframe::new(3.0, vec![1.0], Interpolation::Linear)]).value_at(0.0), vec![1.0]);
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::world::antialiasing::*;
+    use rust_ray_tracer::misc::random::*;
+    use rust_ray_tracer::world::settings::RenderSettings;
+
+    //Tests that grid samples are spread evenly over the pixel
+    #[test]
+    fn grid_offsets() {
+        assert_eq!(Sampler::Grid.offsets(1, &mut LocalRng), vec![(0.5, 0.5)]);
+        assert_eq!(Sampler::Grid.offsets(4, &mut LocalRng), vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)]);
+        assert_eq!(Sampler::Grid.offsets(5, &mut LocalRng).len(), 9);
+
+        //The default sample count fills its grid, so no extra rays are sent
+        let settings = RenderSettings::default();
+        assert_eq!(settings.sampler.offsets(settings.samples, &mut LocalRng).len(), settings.samples);
+    }
+
+    //Tests that jittered samples stay inside their cells and random samples inside the pixel
+    #[test]
+    fn random_offsets() {
+        reseed(3);
+        let jittered = Sampler::Jittered.offsets(4, &mut LocalRng);
+        assert_eq!(jittered.len(), 4);
+        for (index, (u, v)) in jittered.iter().enumerate() {
+            let (cell_u, cell_v) = ((index % 2) as f32 * 0.5, (index / 2) as f32 * 0.5);
+            assert!(*u >= cell_u && *u < cell_u + 0.5);
+            assert!(*v >= cell_v && *v < cell_v + 0.5);
+        }
+        let random = Sampler::Random.offsets(5, &mut LocalRng);
+        assert_eq!(random.len(), 5);
+        assert!(random.iter().all(|(u, v)| (0.0..1.0).contains(u) && (0.0..1.0).contains(v)));
+    }
+
+    //Tests the weights given by each filter
+    #[test]
+    fn filter_weights() {
+        assert_eq!(Filter::Box.weight(0.4, -0.4), 1.0);
+        assert_eq!(Filter::Box.weight(0.6, 0.0), 0.0);
+        assert_eq!(Filter::Tent.weight(0.0, 0.0), 1.0);
+        assert_eq!(Filter::Tent.weight(0.5, 0.0), 0.5);
+        assert!(Filter::Gaussian.weight(0.0, 0.0) > Filter::Gaussian.weight(0.5, 0.0));
+        assert_eq!(Filter::Gaussian.weight(1.6, 0.0), 0.0);
+        assert!(Filter::Mitchell.weight(1.5, 0.0) < 0.0);
+        assert_eq!(Filter::Mitchell.weight(2.5, 0.0), 0.0);
+    }
+
+    //Tests that wider filters spread samples beyond the pixel
+    #[test]
+    fn place_samples() {
+        assert_eq!(Filter::Box.place(&[(0.0, 1.0)]), vec![(0.0, 1.0, 1.0)]);
+        assert_eq!(Filter::Tent.place(&[(0.5, 0.5), (0.0, 0.5)]), vec![(0.5, 0.5, 1.0), (-0.5, 0.5, 0.0)]);
+    }
+}
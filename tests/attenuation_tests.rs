@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::world::attenuation::Attenuation;
+
+    #[test]
+    //Tests the fraction of light left at a distance for each kind of attenuation
+    fn attenuation_factor() {
+        assert_eq!(Attenuation::None.factor(100.0), 1.0);
+        assert_eq!(Attenuation::Linear.factor(4.0), 0.25);
+        assert_eq!(Attenuation::InverseSquare.factor(4.0), 0.0625);
+        let coefficients = Attenuation::Coefficients { constant: 1.0, linear: 0.5, quadratic: 0.25 };
+        assert_eq!(coefficients.factor(2.0), 1.0 / 3.0);
+        assert_eq!(Attenuation::default(), Attenuation::None);
+    }
+
+    #[test]
+    //Tests that points at a light's position do not become infinitely bright
+    fn attenuation_at_zero_distance() {
+        assert!(Attenuation::InverseSquare.factor(0.0).is_finite());
+        assert!(Attenuation::Linear.factor(0.0).is_finite());
+        let zero = Attenuation::Coefficients { constant: 0.0, linear: 0.0, quadratic: 0.0 };
+        assert_eq!(zero.factor(5.0), 1.0);
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::world::background::Background;
+    use rust_ray_tracer::world::scene::Scene;
+    use rust_ray_tracer::objects::plane::Plane;
+    use rust_ray_tracer::core::canvas::Canvas;
+    use rust_ray_tracer::core::color::*;
+    use rust_ray_tracer::core::matrix::Matrix4x4;
+    use rust_ray_tracer::core::vector::Vec4;
+    use rust_ray_tracer::materials::material::Material;
+    use rust_ray_tracer::ray_tracing::ray::Ray;
+    use std::sync::Arc;
+
+    #[test]
+    //Tests the color of each kind of background
+    fn background_colors() {
+        let solid = Background::Color(Color::new(0.2, 0.3, 0.4));
+        assert_eq!(solid.color_at(&Vec4::new(0.0, 1.0, 0.0, 0.0)), Color::new(0.2, 0.3, 0.4));
+
+        let gradient = Background::Gradient { top: WHITE, bottom: BLACK };
+        assert_eq!(gradient.color_at(&Vec4::new(0.0, 1.0, 0.0, 0.0)), WHITE);
+        assert_eq!(gradient.color_at(&Vec4::new(0.0, -1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(gradient.color_at(&Vec4::new(0.0, 0.0, 5.0, 0.0)), Color::new(0.5, 0.5, 0.5));
+
+        //Top half of the image is the sky and the bottom half is the ground
+        let mut canvas = Canvas::new(4, 2);
+        for x in 0..4 {
+            canvas.set(Color::new(0.0, 0.0, 1.0), x, 0);
+            canvas.set(Color::new(0.0, 1.0, 0.0), x, 1);
+        }
+        let environment = Background::Environment(Arc::new(canvas));
+        assert_eq!(environment.color_at(&Vec4::new(0.0, 1.0, 0.0, 0.0)), Color::new(0.0, 0.0, 1.0));
+        assert_eq!(environment.color_at(&Vec4::new(0.3, -2.0, 0.1, 0.0)), Color::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    //Tests that missed and reflected rays see the background
+    fn rays_see_background() {
+        let mut scene = Scene::new();
+        scene.background = Background::Gradient { top: WHITE, bottom: BLACK };
+        let up = Ray::new((0.0, 0.0, 0.0), (0.0, 1.0, 0.0));
+        assert_eq!(Scene::compute_color(up, &scene, 5), Some(WHITE));
+
+        let mut mirror = Material::default();
+        mirror.color = BLACK;
+        mirror.reflectivity = 1.0;
+        mirror.specular = 0.0;
+        scene.objects.push(Box::new(Plane::new(Matrix4x4::identity(), mirror)));
+        let down = Ray::new((0.0, 1.0, 0.0), (0.0, -1.0, 0.0));
+        assert_eq!(Scene::compute_color(down.clone(), &scene, 5).unwrap().round(), WHITE);
+        assert_eq!(Scene::compute_color(down, &scene, 0).unwrap().round(), BLACK);
+    }
+}
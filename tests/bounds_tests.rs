@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::objects::bounds::BoundingBox;
+    use rust_ray_tracer::objects::group::Group;
+    use rust_ray_tracer::objects::sphere::Sphere;
+    use rust_ray_tracer::objects::plane::Plane;
+    use rust_ray_tracer::objects::cone::Cone;
+    use rust_ray_tracer::objects::triangle::Triangle;
+    use rust_ray_tracer::objects::object::*;
+    use rust_ray_tracer::core::matrix::Matrix4x4;
+    use rust_ray_tracer::core::vector::Vec4;
+    use rust_ray_tracer::materials::material::*;
+    use rust_ray_tracer::ray_tracing::ray::Ray;
+    use rust_ray_tracer::world::scene::Scene;
+
+    #[test]
+    //Tests adding points to an empty bounding box
+    fn add_points_to_box() {
+        let mut bounds = BoundingBox::empty();
+        assert!(bounds.is_empty());
+        bounds.add_point(&Vec4(-5.0, 2.0, 0.0, 1.0));
+        bounds.add_point(&Vec4(7.0, 0.0, -3.0, 1.0));
+        assert_eq!(bounds.min, Vec4(-5.0, 0.0, -3.0, 1.0));
+        assert_eq!(bounds.max, Vec4(7.0, 2.0, 0.0, 1.0));
+    }
+
+    #[test]
+    //Tests the bounds of transformed primitives
+    fn primitive_bounds() {
+        let sphere = Sphere::new(Matrix4x4::translation(1.0, -3.0, 5.0) * Matrix4x4::scaling(0.5, 2.0, 4.0), Material::default());
+        let bounds = sphere.bounds();
+        assert_eq!(bounds.min, Vec4(0.5, -5.0, 1.0, 1.0));
+        assert_eq!(bounds.max, Vec4(1.5, -1.0, 9.0, 1.0));
+
+        let cone = Cone::new(Matrix4x4::identity(), Material::default(), -5.0, 3.0, true);
+        let bounds = cone.bounds();
+        assert_eq!(bounds.min, Vec4(-5.0, -5.0, -5.0, 1.0));
+        assert_eq!(bounds.max, Vec4(5.0, 3.0, 5.0, 1.0));
+
+        let triangle = Triangle::new(Vec4(-3.0, 7.0, 2.0, 1.0), Vec4(6.0, 2.0, -4.0, 1.0), Vec4(2.0, -1.0, -1.0, 1.0), Material::default());
+        let bounds = triangle.bounds();
+        assert_eq!(bounds.min, Vec4(-3.0, -1.0, -4.0, 1.0));
+        assert_eq!(bounds.max, Vec4(6.0, 7.0, 2.0, 1.0));
+    }
+
+    #[test]
+    //Tests that planes produce unbounded boxes
+    fn plane_bounds() {
+        let plane = Plane::new(Matrix4x4::translation(0.0, 2.0, 0.0), Material::default());
+        assert!(!plane.bounds().is_finite());
+    }
+
+    #[test]
+    //Tests intersecting rays with a bounding box
+    fn ray_box_intersections() {
+        let bounds = BoundingBox::new(Vec4(5.0, -2.0, 0.0, 1.0), Vec4(11.0, 4.0, 7.0, 1.0));
+        assert!(bounds.intersects(&Ray::new((15.0, 1.0, 2.0), (-1.0, 0.0, 0.0))));
+        assert!(bounds.intersects(&Ray::new((9.0, -1.0, -8.0), (0.0, 0.0, 1.0))));
+        assert!(bounds.intersects(&Ray::new((8.0, 2.0, 3.5), (0.0, 0.0, 1.0))));
+        assert!(!bounds.intersects(&Ray::new((9.0, -1.0, -8.0), (2.0, 4.0, 6.0))));
+        assert!(!bounds.intersects(&Ray::new((12.0, 5.0, 4.0), (0.0, -1.0, 0.0))));
+        assert!(!bounds.intersects(&Ray::new((8.0, 6.0, 12.0), (0.0, 0.0, -1.0))));
+        //Boxes behind the ray still count since negative intersections are used for refraction
+        assert!(bounds.intersects(&Ray::new((8.0, 2.0, 12.0), (0.0, 0.0, 1.0))));
+    }
+
+    #[test]
+    //Tests the bounds of a transformed group containing transformed children
+    fn group_bounds() {
+        let mut group = Group::new(Matrix4x4::scaling(2.0, 2.0, 2.0), Material::default());
+        Sphere::new(Matrix4x4::translation(2.0, 5.0, -3.0), Material::default()).add_to_group(&mut group);
+        Sphere::new(Matrix4x4::translation(-4.0, 0.0, 0.0), Material::default()).add_to_group(&mut group);
+        let bounds = group.bounds();
+        assert_eq!(bounds.min, Vec4(-10.0, -2.0, -8.0, 1.0));
+        assert_eq!(bounds.max, Vec4(6.0, 12.0, 2.0, 1.0));
+    }
+
+    #[test]
+    //Tests that dividing a group builds a hierarchy without changing what rays hit
+    fn divide_group() {
+        let mut group = Group::default();
+        for i in 0..10 {
+            Sphere::new(Matrix4x4::translation(i as f32 * 3.0, 0.0, 0.0), Material::default()).add_to_group(&mut group);
+        }
+        let undivided = group.clone();
+        group.divide(4);
+        assert_eq!(group.objects.len(), 2);
+
+        let ray = Ray::new((12.0, 0.0, -5.0), (0.0, 0.0, 1.0));
+        let before = undivided.intersect(&ray).unwrap();
+        let after = group.intersect(&ray).unwrap();
+        assert_eq!(before.len(), after.len());
+        for (i1, i2) in before.iter().zip(after.iter()) {
+            assert_eq!(i1.t, i2.t);
+            assert_eq!(i1.normal.round(), i2.normal.round());
+        }
+
+        let miss = Ray::new((1.5, 0.0, -5.0), (0.0, 0.0, 1.0));
+        assert_eq!(group.intersect(&miss), None);
+    }
+
+    #[test]
+    //Tests that unbounded children are kept out of subgroups
+    fn divide_with_plane() {
+        let mut group = Group::default();
+        Plane::default().add_to_group(&mut group);
+        for i in 0..6 {
+            Sphere::new(Matrix4x4::translation(i as f32 * 3.0, 2.0, 0.0), Material::default()).add_to_group(&mut group);
+        }
+        group.divide(2);
+        assert_eq!(group.objects.len(), 3);
+        let ray = Ray::new((20.0, 5.0, 0.0), (0.0, -1.0, 0.0));
+        assert_eq!(group.intersect(&ray).unwrap().len(), 1);
+    }
+
+    #[test]
+    //Tests dividing the top level objects of a scene
+    fn divide_scene() {
+        let mut scene = Scene::new();
+        for i in 0..8 {
+            scene.objects.push(Box::new(Sphere::new(Matrix4x4::translation(i as f32 * 3.0, 0.0, 0.0), Material::default())));
+        }
+        scene.divide(4);
+        assert_eq!(scene.objects.len(), 1);
+        let intersections = Ray::intersect_scene(&scene, Ray::new((6.0, 0.0, -5.0), (0.0, 0.0, 1.0)));
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].t, 4.0);
+    }
+}
@@ -1,13 +1,22 @@
 #[cfg(test)]
 
 mod tests {
-    use rust_ray_tracer::world::camera::Camera;
+    use rust_ray_tracer::world::camera::*;
     use rust_ray_tracer::core::vector::Vec4;
     use rust_ray_tracer::core::matrix::Matrix4x4;
     use rust_ray_tracer::misc::axis::Axis;
     use rust_ray_tracer::world::scene::Scene;
     use rust_ray_tracer::core::canvas::Canvas;
-    use rust_ray_tracer::core::color::Color;
+    use rust_ray_tracer::core::color::*;
+    use rust_ray_tracer::objects::motion::Motion;
+    use rust_ray_tracer::objects::sphere::Sphere;
+    use rust_ray_tracer::materials::material::Material;
+    use rust_ray_tracer::world::background::Background;
+    use rust_ray_tracer::misc::random::*;
+    use rust_ray_tracer::world::lighting::AreaLight;
+    use rust_ray_tracer::world::settings::Integrator;
+    use rand::Rng;
+    use std::f32::consts::FRAC_1_SQRT_2;
 
     //Tests the pixel size of a new camera
     #[test]
@@ -44,6 +53,130 @@ mod tests {
         assert_eq!(ray.direction.round(), Vec4::new((2 as f32).sqrt() / 2.0, 0.0, - (2 as f32).sqrt() / 2.0, 0.0).round());
     }
 
+    //Tests that orthographic rays are parallel and start across the view
+    #[test]
+    fn orthographic_rays() {
+        let camera = Camera::orthographic(200, 100, 4.0, 2.0);
+        let center = Camera::ray_towards_pixel_raw(&camera, 100, 50, 0.0, 0.0);
+        assert_eq!(center.origin, Vec4::new(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(center.direction, Vec4::new(0.0, 0.0, -1.0, 0.0));
+        let corner = Camera::ray_towards_pixel_raw(&camera, 0, 0, 0.0, 0.0);
+        assert_eq!(corner.origin, Vec4::new(2.0, 1.0, 0.0, 1.0));
+        assert_eq!(corner.direction, Vec4::new(0.0, 0.0, -1.0, 0.0));
+
+        let mut resized = Camera::orthographic(200, 100, 4.0, 2.0);
+        resized.resize(100, 50, 60.0);
+        assert_eq!(resized.projection, Projection::Orthographic);
+        assert_eq!((resized.half_width, resized.half_height), (2.0, 1.0));
+        assert_eq!(Camera::ray_towards_pixel_raw(&resized, 0, 0, 0.0, 0.0).origin, corner.origin);
+    }
+
+    //Tests that equirectangular rays cover every direction around the camera
+    #[test]
+    fn equirectangular_rays() {
+        let camera = Camera::equirectangular(360, 180);
+        assert_eq!(Camera::ray_towards_pixel_raw(&camera, 180, 90, 0.0, 0.0).direction, Vec4::new(0.0, 0.0, -1.0, 0.0));
+        assert_eq!(Camera::ray_towards_pixel_raw(&camera, 270, 90, 0.0, 0.0).direction.round(), Vec4::new(-1.0, 0.0, 0.0, 0.0));
+        assert_eq!(Camera::ray_towards_pixel_raw(&camera, 0, 90, 0.0, 0.0).direction.round(), Vec4::new(0.0, 0.0, 1.0, 0.0));
+        assert_eq!(Camera::ray_towards_pixel_raw(&camera, 180, 0, 0.0, 0.0).direction.round(), Vec4::new(0.0, 1.0, 0.0, 0.0));
+        assert_eq!(camera.field_of_view(), 360.0);
+    }
+
+    //Tests that fisheye rays spread evenly to the edge of the circle and pixels outside it are not rendered
+    #[test]
+    fn fisheye_rays() {
+        let camera = Camera::fisheye(200, 100, 180.0);
+        assert_eq!(Camera::ray_towards_pixel_raw(&camera, 100, 50, 0.0, 0.0).direction.round(), Vec4::new(0.0, 0.0, -1.0, 0.0));
+        assert_eq!(Camera::ray_towards_pixel_raw(&camera, 50, 50, 0.0, 0.0).direction.round(), Vec4::new(1.0, 0.0, 0.0, 0.0).round());
+        assert_eq!(Camera::ray_towards_pixel_raw(&camera, 100, 75, 0.0, 0.0).direction.round(), Vec4::new(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2, 0.0).round());
+        assert!(Camera::in_view(&camera, 100, 0, 0.0, 0.0));
+        assert!(!Camera::in_view(&camera, 0, 0, 0.0, 0.0));
+
+        let mut scene = Scene::default();
+        scene.settings.show_progress = false;
+        let mut canvas = Canvas::new(200, 100);
+        canvas.set(Color::new(1.0, 0.0, 0.0), 0, 0);
+        Camera::render(&camera, &scene, &mut canvas);
+        assert_eq!(canvas.get(0, 0), Some(&Color::new(1.0, 0.0, 0.0)));
+    }
+
+    //Tests that rays are sent at times across the shutter interval from where the camera is at that time
+    #[test]
+    fn shutter_rays() {
+        let mut camera = Camera::new(201, 101, 90.0);
+        assert_eq!(Camera::ray_towards_pixel(&camera, 100, 50).time, 0.0);
+        camera.shutter_open = 0.25;
+        camera.shutter_close = 0.75;
+        camera.motion = Some(Motion::new(Matrix4x4::translation(-4.0, 0.0, 0.0)));
+        reseed(4);
+        for _ in 0..20 {
+            let ray = Camera::ray_towards_pixel(&camera, 100, 50);
+            assert!(ray.time >= 0.25 && ray.time < 0.75);
+            assert!((ray.origin.0 - 4.0 * ray.time).abs() < 0.0001);
+        }
+    }
+
+    //Tests that a moving sphere is blurred along its path
+    #[test]
+    fn motion_blur() {
+        let mut scene = Scene::default();
+        scene.objects.clear();
+        scene.settings.show_progress = false;
+        scene.settings.samples = 16;
+        scene.background = Background::Color(BLACK);
+        let mut sphere = Sphere::new(Matrix4x4::translation(-1.0, 0.0, 0.0), Material::default());
+        sphere.material.ambient = 1.0;
+        sphere.material.color = WHITE;
+        sphere.motion = Some(Motion::new(Matrix4x4::translation(1.0, 0.0, 0.0)));
+        scene.objects.push(Box::new(sphere));
+        let mut camera = Camera::new(9, 9, 90.0);
+        camera.transform(Matrix4x4::view_transform(
+            Vec4::new(0.0, 0.0, -4.0, 1.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+            Vec4::new(0.0, 1.0, 0.0, 0.0),
+        ));
+        let mut still = Canvas::new(9, 9);
+        Camera::render_supersampled(&camera, &scene, &mut still);
+        camera.shutter_close = 1.0;
+        let mut blurred = Canvas::new(9, 9);
+        Camera::render_supersampled(&camera, &scene, &mut blurred);
+        let lit = still.get(2, 4).unwrap().0;
+        assert!(lit > 0.0);
+        assert_eq!(still.get(6, 4), Some(&BLACK));
+        let edge = blurred.get(6, 4).unwrap().0;
+        assert!(edge > 0.0 && edge < lit);
+    }
+
+    //Tests that rays through a lens start on the aperture and meet at the focal distance
+    #[test]
+    fn lens_rays() {
+        let mut camera = Camera::new(201, 101, 90.0);
+        camera.aperture = 0.5;
+        camera.focal_distance = 4.0;
+        reseed(1);
+        for shape in [ApertureShape::Disk, ApertureShape::Polygon(6)].iter() {
+            camera.aperture_shape = *shape;
+            for _ in 0..20 {
+                let ray = Camera::ray_towards_pixel(&camera, 100, 50);
+                assert_eq!(ray.origin.2, 0.0);
+                assert!(ray.origin.0.hypot(ray.origin.1) <= 0.5);
+                let distance = -4.0 / ray.direction.2;
+                let focus = &ray.origin + &ray.direction * distance;
+                assert_eq!(focus.round(), Vec4::new(0.0, 0.0, -4.0, 1.0).round());
+            }
+        }
+    }
+
+    //Tests that polygonal apertures stay inside their sides
+    #[test]
+    fn polygon_aperture() {
+        reseed(2);
+        for _ in 0..100 {
+            let (x, y) = ApertureShape::Polygon(4).sample(&mut LocalRng);
+            assert!(x.abs() + y.abs() <= 1.0001);
+        }
+    }
+
     //Tests rendering a scene
     #[test]
     fn render_scene() {
@@ -57,4 +190,104 @@ mod tests {
         Camera::render(&camera, &scene, &mut canvas);
         assert_eq!(canvas.get(5, 5).unwrap().round(), Color::new(0.38072, 0.47583, 0.2855).round());
     }
-}
\ No newline at end of file
+
+    //Tests that rendering with several threads matches rendering with one
+    #[test]
+    fn threaded_render_matches() {
+        let mut scene = Scene::default();
+        let mut camera = Camera::new(37, 21, 90.0);
+        let start_pos = Vec4::new(0.0, 0.0, -5.0, 1.0);
+        let end_pos = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        let up_vec = Vec4::new(0.0, 1.0, 0.0, 0.0);
+        camera.transform(Matrix4x4::view_transform(start_pos, end_pos, up_vec));
+
+        scene.settings.threads = 1;
+        let mut single = Canvas::new(37, 21);
+        Camera::render(&camera, &scene, &mut single);
+
+        scene.settings.threads = 4;
+        let mut threaded = Canvas::new(37, 21);
+        Camera::render(&camera, &scene, &mut threaded);
+
+        assert_eq!(single.contents, threaded.contents);
+        assert_eq!(threaded.get(18, 10).unwrap().round(), Color::new(0.38072, 0.47583, 0.2855).round());
+    }
+
+    //Tests that adaptive renders only send more rays through pixels on edges
+    #[test]
+    fn adaptive_render() {
+        let mut scene = Scene::default();
+        scene.settings.show_progress = false;
+        let mut camera = Camera::new(21, 21, 90.0);
+        camera.transform(Matrix4x4::view_transform(
+            Vec4::new(0.0, 0.0, -5.0, 1.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+            Vec4::new(0.0, 1.0, 0.0, 0.0),
+        ));
+        let mut single = Canvas::new(21, 21);
+        Camera::render(&camera, &scene, &mut single);
+
+        let mut canvas = Canvas::new(21, 21);
+        let heatmap = Camera::render_adaptive(&camera, &scene, &mut canvas);
+        assert_eq!(canvas.get(0, 0), single.get(0, 0));
+        assert_eq!(heatmap.get(0, 0), Some(&Color::new(0.0, 0.0, 1.0)));
+        assert!(heatmap.contents.iter().any(|color| color.0 > 0.0));
+        assert_ne!(canvas, single);
+
+        scene.settings.adaptive_depth = 0;
+        let heatmap = Camera::render_adaptive(&camera, &scene, &mut canvas);
+        assert_eq!(canvas, single);
+        assert!(heatmap.contents.iter().all(|color| *color == Color::new(0.0, 0.0, 1.0)));
+    }
+
+    //Renders the default scene lit by a jittered area light
+    fn render_seeded(seed: u64, threads: usize, integrator: Integrator) -> Canvas {
+        let mut scene = Scene::default();
+        scene.light_sources = vec![Box::new(AreaLight::new(
+            Vec4::new(-10.0, 10.0, -10.0, 1.0),
+            Vec4::new(4.0, 0.0, 0.0, 0.0),
+            2,
+            Vec4::new(0.0, 4.0, 0.0, 0.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        ))];
+        scene.settings.seed = seed;
+        scene.settings.threads = threads;
+        scene.settings.show_progress = false;
+        scene.settings.integrator = integrator;
+        scene.settings.samples = 2;
+        let mut camera = Camera::new(24, 16, 90.0);
+        camera.transform(Matrix4x4::view_transform(
+            Vec4::new(0.0, 0.0, -3.0, 1.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+            Vec4::new(0.0, 1.0, 0.0, 0.0),
+        ));
+        let mut canvas = Canvas::new(24, 16);
+        Camera::render(&camera, &scene, &mut canvas);
+        canvas
+    }
+
+    //Tests that renders with the same seed are identical no matter how many threads are used
+    #[test]
+    fn seeded_renders() {
+        for integrator in [Integrator::Whitted, Integrator::PathTracer].iter() {
+            let first = render_seeded(7, 1, *integrator);
+            assert_eq!(render_seeded(7, 1, *integrator), first);
+            assert_eq!(render_seeded(7, 4, *integrator), first);
+            assert_ne!(render_seeded(8, 4, *integrator), first);
+        }
+    }
+
+    //Tests that each pixel gets its own random numbers
+    #[test]
+    fn pixel_seeds() {
+        assert_eq!(pixel_seed(1, 2, 3), pixel_seed(1, 2, 3));
+        assert_ne!(pixel_seed(1, 2, 3), pixel_seed(1, 3, 2));
+        assert_ne!(pixel_seed(1, 2, 3), pixel_seed(2, 2, 3));
+
+        reseed(5);
+        let first: f32 = LocalRng.gen();
+        reseed(5);
+        assert_eq!(LocalRng.gen::<f32>(), first);
+    }
+}
@@ -37,4 +37,95 @@ mod tests {
         let result = Canvas::format_ppm(canvas);
         assert_eq!(result.chars().last().unwrap(), '\n')
     }
-}
\ No newline at end of file
+
+    //Tests the header and pixel bytes of a binary ppm
+    #[test]
+    fn ppm_binary() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set(Color::new(1.5, 0.5, -0.5), 1, 0);
+        let result = Canvas::format_ppm_binary(&canvas);
+        assert_eq!(&result[..11], b"P6\n2 1\n255\n");
+        assert_eq!(&result[11..], &[0, 0, 0, 255, 128, 0]);
+    }
+
+    //Tests the signature and chunks of a generated png
+    #[test]
+    fn png_chunks() {
+        let canvas = Canvas::new(3, 2);
+        let result = Canvas::format_png(&canvas);
+        assert_eq!(&result[..8], &[137, 80, 78, 71, 13, 10, 26, 10]);
+        assert_eq!(&result[12..16], b"IHDR");
+        assert_eq!(&result[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+        //An empty IEND chunk always ends with the same checksum
+        assert_eq!(&result[(result.len() - 12)..], &[0, 0, 0, 0, 73, 69, 78, 68, 174, 66, 96, 130]);
+    }
+
+    //Tests that the output format comes from the file extension
+    #[test]
+    fn write_file_extension() {
+        let canvas = Canvas::new(4, 4);
+        let path = std::env::temp_dir().join("rust_ray_tracer_canvas_test.png");
+        Canvas::write_file(&canvas, path.to_str().unwrap()).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(&bytes[1..4], b"PNG");
+        std::fs::remove_file(&path).unwrap();
+        assert!(Canvas::write_file(&canvas, "image.bmp").is_err());
+        assert!(Canvas::write_file(&canvas, "image").is_err());
+    }
+
+    //Creates a canvas with a different color in each pixel
+    fn gradient_canvas() -> Canvas {
+        let mut canvas = Canvas::new(5, 3);
+        for y in 0..3 {
+            for x in 0..5 {
+                canvas.set(Color::new_255(x * 50, y * 100, 255 - x * 10), x, y);
+            }
+        }
+        canvas
+    }
+
+    //Tests reading back written ppm and png images
+    #[test]
+    fn read_written_images() {
+        let canvas = gradient_canvas();
+        assert_eq!(Canvas::parse_ppm(&Canvas::format_ppm_binary(&canvas)).unwrap(), canvas);
+        assert_eq!(Canvas::parse_ppm(Canvas::format_ppm(canvas.clone()).as_bytes()).unwrap(), canvas);
+        assert_eq!(Canvas::parse_png(&Canvas::format_png(&canvas)).unwrap(), canvas);
+    }
+
+    //Tests reading a ppm with comments and a different maximum value
+    #[test]
+    fn read_ppm_comments() {
+        let canvas = Canvas::parse_ppm(b"P3\n# comment\n2 1 # size\n10\n10 5 0\n0 0 10\n").unwrap();
+        assert_eq!(canvas.get(0, 0), Some(&Color::new(1.0, 0.5, 0.0)));
+        assert_eq!(canvas.get(1, 0), Some(&Color::new(0.0, 0.0, 1.0)));
+        assert!(Canvas::parse_ppm(b"P3\n2 1\n255\n0 0 0\n").is_err());
+        assert!(Canvas::parse_ppm(b"P5\n1 1\n255\n0").is_err());
+    }
+
+    //Tests rejecting ppm headers which are larger than the pixel data that follows them
+    #[test]
+    fn read_ppm_bad_size() {
+        let error = Canvas::parse_ppm(b"P6 100000 100000 255\n").unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(Canvas::parse_ppm(b"P3 100000 100000 255\n").is_err());
+        assert!(Canvas::parse_ppm(b"P6 2 1 255\n\x00\x00\x00").is_err());
+        assert!(Canvas::parse_ppm(b"P6 2 1 65535\n\x00\x00\x00\x00\x00\x00").is_err());
+        let huge = format!("P6 {} {} 255\n", usize::MAX, usize::MAX);
+        assert_eq!(Canvas::parse_ppm(huge.as_bytes()).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+        assert!(Canvas::parse_ppm(b"P6 0 0 255\n").is_err());
+        assert!(Canvas::parse_ppm(b"P3 0 4 255\n").is_err());
+    }
+
+    //Tests reading pngs written by other encoders
+    #[test]
+    fn read_png_files() {
+        let canvas = Canvas::read_file("tests/test_rgba.png").unwrap();
+        assert_eq!(canvas.width, 16);
+        assert_eq!(canvas.height, 8);
+        assert_eq!(canvas.get(3, 5), Some(&Color::new_255(48, 160, 15)));
+
+        let canvas = Canvas::read_file("tests/test_palette.png").unwrap();
+        assert_eq!(canvas.contents[..4], [Color::new(1.0, 0.0, 0.0), Color::new(0.0, 1.0, 0.0), Color::new(0.0, 0.0, 1.0), Color::new(1.0, 0.0, 0.0)]);
+    }
+}
@@ -7,6 +7,7 @@ mod tests {
     use rust_ray_tracer::core::vector::Vec4;
     use rust_ray_tracer::materials::material::*;
     use rust_ray_tracer::ray_tracing::ray::Ray;
+    use rust_ray_tracer::objects::motion::Motion;
 
     #[test]
     //Tests ray intersections with each face of a cube and from within inside a cube
@@ -66,4 +67,17 @@ mod tests {
         assert_eq!(cube.normal(&Vec4(-1.0, -0.2, 0.9, 1.0), None, None), Vec4(-1.0, 0.0, 0.0, 0.0));
         assert_eq!(cube.normal(&Vec4(-0.4, 1.0, -0.1, 1.0), None, None), Vec4(0.0, 1.0, 0.0, 0.0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    //Tests intersecting a cube which grows over the shutter interval
+    fn moving_cube() {
+        let mut cube = Cube::new(Matrix4x4::identity(), Material::default());
+        cube.motion = Some(Motion::new(Matrix4x4::scaling(3.0, 3.0, 3.0)));
+        let ray = Ray::new((2.0, 0.0, -5.0), (0.0, 0.0, 1.0));
+        assert!(cube.intersect(&ray).is_none());
+        let hits = cube.intersect(&ray.at_time(1.0)).unwrap();
+        assert!((hits[0].t - 2.0).abs() < 0.0001 && (hits[1].t - 8.0).abs() < 0.0001);
+        assert_eq!(hits[0].normal.round(), Vec4::new(0.0, 0.0, -1.0, 0.0).round());
+        assert_eq!(cube.get_inverse_at(0.5).round(), Matrix4x4::scaling(0.5, 0.5, 0.5));
+    }
+}
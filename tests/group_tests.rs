@@ -9,6 +9,7 @@ mod tests {
     use rust_ray_tracer::misc::axis::Axis;
     use rust_ray_tracer::materials::material::*;
     use rust_ray_tracer::ray_tracing::ray::Ray;
+    use rust_ray_tracer::objects::motion::Motion;
 
     #[test]
     //Tests creating a group
@@ -22,6 +23,25 @@ mod tests {
         assert_eq!(group.objects.len(), 1);
     }
 
+    #[test]
+    //Tests that children added or moved after an intersection are still hit
+    fn add_after_intersect() {
+        let mut group = Group::default();
+        Sphere::default().add_to_group(&mut group);
+        let ray = Ray::new((5.0, 0.0, -5.0), (0.0, 0.0, 1.0));
+        assert!(group.intersect(&ray).is_none());
+        Sphere::new(Matrix4x4::translation(5.0, 0.0, 0.0), Material::default()).add_to_group(&mut group);
+        assert_eq!(group.intersect(&ray).unwrap().len(), 2);
+
+        let ray = Ray::new((-5.0, 0.0, -5.0), (0.0, 0.0, 1.0));
+        assert!(group.intersect(&ray).is_none());
+        let mut moved = Sphere::new(Matrix4x4::translation(-5.0, 0.0, 0.0), Material::default());
+        moved.push_parent_inverse(group.inverse.clone());
+        group.objects[0] = Box::new(moved);
+        group.reset_bounds();
+        assert_eq!(group.intersect(&ray).unwrap().len(), 2);
+    }
+
     #[test]
     //Tests intersecting a ray with a group
     fn ray_group_intersections() {
@@ -117,4 +137,28 @@ mod tests {
         let intersections2 = sphere_clone.intersect(&ray);
         assert_eq!(intersections1.unwrap()[0].normal, intersections2.unwrap()[0].normal);
     }
-}
\ No newline at end of file
+
+    #[test]
+    //Tests that moving groups carry their children and turn their normals
+    fn moving_group() {
+        let mut group = Group::new(Matrix4x4::identity(), Material::default());
+        Sphere::new(Matrix4x4::translation(0.0, 0.0, 2.0), Material::default()).add_to_group(&mut group);
+        group.motion = Some(Motion::new(Matrix4x4::rotation(Axis::Y, 90.0)));
+        let ray = Ray::new((-5.0, 0.0, 2.0), (1.0, 0.0, 0.0));
+        assert_eq!(group.intersect(&ray).unwrap().len(), 2);
+        assert!(group.intersect(&ray.clone().at_time(1.0)).is_none());
+
+        let ray = Ray::new((-5.0, 0.0, 0.0), (0.0, 0.0, 1.0)).at_time(1.0);
+        assert!(group.intersect(&ray).is_none());
+        let ray = Ray::new((2.0, 0.0, -5.0), (0.0, 0.0, 1.0)).at_time(1.0);
+        let hits = group.intersect(&ray).unwrap();
+        assert_eq!(hits[0].t.round(), 4.0);
+        assert_eq!(hits[0].normal.round(), Vec4::new(0.0, 0.0, -1.0, 0.0).round());
+
+        //Moving groups keep their normals when a scene puts them inside an untransformed group
+        let mut outer = Group::default();
+        outer.objects.push(Box::new(group.clone()));
+        assert_eq!(outer.intersect(&ray).unwrap()[0].normal, hits[0].normal);
+        assert!(group.bounds().contains_point(&Vec4::new(2.0, 0.0, 0.0, 1.0)));
+    }
+}
@@ -8,6 +8,7 @@ mod tests {
     use rust_ray_tracer::world::lighting::*;
     use rust_ray_tracer::objects::sphere::Sphere;
     use rust_ray_tracer::objects::plane::Plane;
+    use rust_ray_tracer::objects::group::Group;
     use rust_ray_tracer::objects::object::*;
     use rust_ray_tracer::world::scene::Scene;
     use rust_ray_tracer::materials::material::Material;
@@ -16,6 +17,9 @@ mod tests {
     use rust_ray_tracer::core::comp::Comp;
     use rust_ray_tracer::ray_tracing::intersection::Intersection;
     use rust_ray_tracer::materials::patterns::*;
+    use rust_ray_tracer::world::attenuation::Attenuation;
+    use rust_ray_tracer::world::settings::RenderSettings;
+    use std::f32::consts::FRAC_1_SQRT_2;
 
     //Tests shadows when sphere does not block the light source from the point
     #[test]
@@ -227,6 +231,7 @@ mod tests {
                     Material::default(),
                 )),
             ],
+            ..Scene::new()
         };
 
         let ray = Ray::new((0.0, 0.0, -5.0), (0.0, 0.0, 1.0));
@@ -282,6 +287,7 @@ mod tests {
                     Material::default(),
                 )),
             ],
+            ..Scene::new()
         };
 
         let ray = Ray::new((0.0, 0.0, (2.0 as f32).sqrt() / 2.0), (0.0, 1.0, 0.0));
@@ -339,6 +345,7 @@ mod tests {
                     material,
                 )),
             ],
+            ..Scene::new()
         };
 
         let ray = Ray::new((0.0, 0.0, 0.1), (0.0, 1.0, 0.0));
@@ -378,6 +385,19 @@ mod tests {
     #[test]
     //Tests color with refraction
     fn color_with_refraction() {
+        let color = floor_over_ball_color(RenderSettings { transparent_shadows: false, ..RenderSettings::default() });
+        assert_eq!(color.round(), Color(0.93642, 0.68642, 0.68642).round());
+    }
+
+    #[test]
+    //Tests that a half transparent floor lets light through to the ball below it
+    fn color_with_transparent_shadow() {
+        let color = floor_over_ball_color(RenderSettings::default());
+        assert_eq!(color.round(), Color(1.12546, 0.68642, 0.68642).round());
+    }
+
+    //Finds the color seen through a refractive floor with a ball below it
+    fn floor_over_ball_color(settings: RenderSettings) -> Color {
         let mut material = Material::default();
         material.transparency = 1.0;
         material.refractive_index = 1.5;
@@ -423,6 +443,8 @@ mod tests {
                 Box::new(floor),
                 Box::new(ball),
             ],
+            settings,
+            ..Scene::new()
         };
 
         let ray = Ray::new((0.0, 0.0, -3.0), (0.0, -((2.0 as f32).sqrt() / 2.0), (2.0 as f32).sqrt() / 2.0));
@@ -437,7 +459,388 @@ mod tests {
         ];
 
         let comps = Comp::compute_vars(intersections[0].clone(), &ray, &intersections);
+        Scene::scene_lighting(&scene, &comps, 5)
+    }
+
+    #[test]
+    //Tests that environment lighting only applies to rays with every bounce remaining
+    fn environment_respects_max_depth() {
+        let mut floor_material = Material::default();
+        floor_material.environment_lighting = 1.0;
+        let mut scene = Scene {
+            objects: vec![
+                Box::new(Plane::new(Matrix4x4::identity(), floor_material)),
+                Box::new(Sphere::new(Matrix4x4::translation(0.0, 1.25, 0.0) * Matrix4x4::scaling(0.5, 0.5, 0.5), Material::default())),
+            ],
+            ..Scene::default()
+        };
+        scene.settings.max_depth = 3;
+        let ray = Ray::new((0.0, 5.0, -5.0), (0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let intersections = Ray::intersect_scene(&scene, ray.clone());
+        let hit = Intersection::hit(&intersections).unwrap();
+        let comps = Comp::compute_vars(hit, &ray, &intersections);
+        assert_ne!(environment_color(&scene, &comps, 3), BLACK);
+        assert_eq!(environment_color(&scene, &comps, 2), BLACK);
+
+        scene.settings.max_depth = 0;
+        assert_eq!(environment_color(&scene, &comps, 0), BLACK);
+    }
+
+    //Creates a scene where a sphere at the origin blocks the light from a second sphere behind it
+    fn shadowed_scene() -> Scene {
+        Scene {
+            light_sources: vec![Box::new(PointLight::new(WHITE, Vec4::new(0.0, 0.0, -10.0, 1.0)))],
+            objects: vec![
+                Box::new(Sphere::new(Matrix4x4::identity(), Material::default())),
+                Box::new(Sphere::new(Matrix4x4::translation(0.0, 0.0, 10.0), Material::default())),
+            ],
+            ..Scene::default()
+        }
+    }
+
+    #[test]
+    //Tests that a PointLight gives no light to points in shadow and full light to lit points
+    fn point_light_intensity() {
+        let scene = Scene::default();
+        let light = &scene.light_sources[0];
+        assert_eq!(light.light_intensity(&Vec4::new(10.0, -10.0, 10.0, 1.0), &scene), BLACK);
+        assert_eq!(light.light_intensity(&Vec4::new(0.0, 10.0, 0.0, 1.0), &scene), WHITE);
+    }
+
+    #[test]
+    //Tests that a point in the shadow of a PointLight only gets ambient light
+    fn point_light_shadow() {
+        let scene = shadowed_scene();
+        let ray = Ray::new((0.0, 0.0, 5.0), (0.0, 0.0, 1.0));
+        let i = Intersection::new(
+            4.0,
+            Ray::position(&ray, 4.0),
+            scene.objects[1].normal(&Ray::position(&ray, 4.0), None, None),
+            &*scene.objects[1],
+        );
+        let comps = Comp::compute_vars(i.clone(), &ray, &vec![i]);
         let color = Scene::scene_lighting(&scene, &comps, 5);
-        assert_eq!(color.round(), Color(0.93642, 0.68642, 0.68642).round());
+        assert_eq!(color.round(), Color(0.1, 0.1, 0.1).round());
+    }
+
+    #[test]
+    //Tests that the shadow floor stops shadows from being darker than it allows
+    fn shadow_floor_lifts_shadows() {
+        let mut scene = shadowed_scene();
+        let ray = Ray::new((0.0, 0.0, 5.0), (0.0, 0.0, 1.0));
+        let i = Intersection::new(
+            4.0,
+            Ray::position(&ray, 4.0),
+            scene.objects[1].normal(&Ray::position(&ray, 4.0), None, None),
+            &*scene.objects[1],
+        );
+        let comps = Comp::compute_vars(i.clone(), &ray, &vec![i]);
+        let dark = Scene::scene_lighting(&scene, &comps, 5);
+        scene.settings.shadow_floor = 0.5;
+        let lifted = Scene::scene_lighting(&scene, &comps, 5);
+        scene.settings.shadow_floor = 1.0;
+        let unshadowed = Scene::scene_lighting(&scene, &comps, 5);
+        assert!(lifted.0 > dark.0 && lifted.0 < unshadowed.0);
+        assert_eq!(unshadowed.round(), Color(1.9, 1.9, 1.9).round());
+    }
+
+    #[test]
+    //Tests that the shadow floor does not light points outside a spot light's cone or behind a disk light
+    fn shadow_floor_keeps_falloff() {
+        let spot = SpotLight::new(WHITE, Vec4::new(0.0, 5.0, 0.0, 1.0), Vec4::new(0.0, -1.0, 0.0, 0.0), 10.0, 20.0);
+        let disk = DiskLight::new(Vec4::new(0.0, -5.0, 0.0, 1.0), Vec4::new(0.0, -1.0, 0.0, 0.0), 1.0, 2, 2, WHITE);
+        let lights: Vec<Box<dyn Light>> = vec![Box::new(spot), Box::new(disk)];
+        for light in lights {
+            let mut scene = Scene::new();
+            scene.objects.push(Box::new(Plane::new(Matrix4x4::identity(), Material::default())));
+            scene.light_sources.push(light);
+            let ray = Ray::new((10.0, 1.0, 0.0), (0.0, -1.0, 0.0));
+            let i = Intersection::new(1.0, Ray::position(&ray, 1.0), scene.objects[0].normal(&Ray::position(&ray, 1.0), None, None), &*scene.objects[0]);
+            let comps = Comp::compute_vars(i.clone(), &ray, &vec![i]);
+            let dark = Scene::scene_lighting(&scene, &comps, 5);
+            scene.settings.shadow_floor = 0.5;
+            assert_eq!(Scene::scene_lighting(&scene, &comps, 5), dark);
+            assert_eq!(dark.round(), Color(0.1, 0.1, 0.1).round());
+        }
+    }
+
+    //Creates a scene with a light above the origin and the given objects between them
+    fn occluded_scene(objects: Vec<Box<dyn Object>>) -> Scene {
+        Scene {
+            light_sources: vec![Box::new(PointLight::new(WHITE, Vec4::new(0.0, 10.0, 0.0, 1.0)))],
+            objects,
+            ..Scene::default()
+        }
+    }
+
+    #[test]
+    //Tests that clear glass lets all of the light through
+    fn glass_shadow() {
+        let mut glass = Sphere::glass();
+        glass.transform = Matrix4x4::translation(0.0, 5.0, 0.0);
+        glass.inverse = glass.transform.inverse().unwrap();
+        let scene = occluded_scene(vec![Box::new(glass)]);
+        let point = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(shadow_transmittance(scene.light_sources[0].get_position(), &point, &scene, 0.0), WHITE);
+        assert!(!in_shadow(scene.light_sources[0].get_position(), &point, &scene));
+    }
+
+    #[test]
+    //Tests that tinted transparent objects filter the light passing through each of their surfaces
+    fn colored_shadow() {
+        let mut material = Material::default();
+        material.color = Color::new(1.0, 0.5, 0.0);
+        material.transparency = 0.8;
+        let scene = occluded_scene(vec![Box::new(Sphere::new(Matrix4x4::translation(0.0, 5.0, 0.0), material))]);
+        let color = scene.light_sources[0].light_intensity(&Vec4::new(0.0, 0.0, 0.0, 1.0), &scene);
+        assert_eq!(color.round(), Color(0.64, 0.16, 0.0).round());
+    }
+
+    #[test]
+    //Tests that every object between a point and the light is accounted for
+    fn shadow_accumulates_occluders() {
+        let mut material = Material::default();
+        material.transparency = 0.5;
+        let scene = occluded_scene(vec![
+            Box::new(Sphere::new(Matrix4x4::translation(0.0, 3.0, 0.0), material.clone())),
+            Box::new(Sphere::new(Matrix4x4::translation(0.0, 6.0, 0.0), material)),
+        ]);
+        let light = &scene.light_sources[0];
+        let point = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(light.light_intensity(&point, &scene).round(), Color(0.0625, 0.0625, 0.0625).round());
+
+        //An opaque object behind a transparent one still blocks the light
+        let mut glass = Sphere::glass();
+        glass.transform = Matrix4x4::translation(0.0, 3.0, 0.0);
+        glass.inverse = glass.transform.inverse().unwrap();
+        let scene = occluded_scene(vec![
+            Box::new(glass),
+            Box::new(Sphere::new(Matrix4x4::translation(0.0, 6.0, 0.0), Material::default())),
+        ]);
+        assert_eq!(scene.light_sources[0].light_intensity(&point, &scene), BLACK);
+    }
+
+    #[test]
+    //Tests that objects in a group filter shadows with the group's material, as they are shaded with it
+    fn grouped_shadow() {
+        let mut material = Material::default();
+        material.color = Color::new(1.0, 0.5, 0.0);
+        material.transparency = 0.8;
+        let mut group = Group::new(Matrix4x4::identity(), material);
+        Sphere::new(Matrix4x4::translation(0.0, 5.0, 0.0), Material::default()).add_to_group(&mut group);
+        let scene = occluded_scene(vec![Box::new(group)]);
+        let color = scene.light_sources[0].light_intensity(&Vec4::new(0.0, 0.0, 0.0, 1.0), &scene);
+        assert_eq!(color.round(), Color(0.64, 0.16, 0.0).round());
+    }
+
+    #[test]
+    //Tests that objects behind the light or which do not cast shadows are ignored
+    fn shadow_ignores_non_occluders() {
+        let mut material = Material::default();
+        material.casts_shadows = false;
+        let scene = occluded_scene(vec![
+            Box::new(Sphere::new(Matrix4x4::translation(0.0, 5.0, 0.0), material)),
+            Box::new(Sphere::new(Matrix4x4::translation(0.0, 15.0, 0.0), Material::default())),
+        ]);
+        let point = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(scene.light_sources[0].light_intensity(&point, &scene), WHITE);
+    }
+
+    #[test]
+    //Tests that a SpotLight is bright inside its inner cone, fades out to its outer cone and is dark beyond it
+    fn spot_light_falloff() {
+        let light = SpotLight::new(WHITE, Vec4::new(0.0, 10.0, 0.0, 1.0), Vec4::new(0.0, -1.0, 0.0, 0.0), 20.0, 40.0);
+        assert_eq!(light.falloff(&Vec4::new(0.0, 0.0, 0.0, 1.0)), 1.0);
+        let edge = 10.0 * 30.0_f32.to_radians().tan();
+        let middle = light.falloff(&Vec4::new(edge, 0.0, 0.0, 1.0));
+        assert!(middle > 0.0 && middle < 1.0);
+        assert_eq!(light.falloff(&Vec4::new(10.0, 0.0, 0.0, 1.0)), 0.0);
+        assert_eq!(light.falloff(&Vec4::new(0.0, 20.0, 0.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    //Tests that SpotLights are blocked by objects in their cone
+    fn spot_light_shadow() {
+        let light = SpotLight::new(WHITE, Vec4::new(0.0, 10.0, 0.0, 1.0), Vec4::new(0.0, -1.0, 0.0, 0.0), 20.0, 40.0);
+        let scene = occluded_scene(vec![Box::new(Sphere::new(Matrix4x4::translation(0.0, 5.0, 0.0), Material::default()))]);
+        assert_eq!(light.light_intensity(&Vec4::new(0.0, 0.0, 0.0, 1.0), &scene), BLACK);
+        assert_eq!(light.light_intensity(&Vec4::new(0.0, 0.0, 0.0, 1.0), &occluded_scene(vec![])), WHITE);
+    }
+
+    #[test]
+    //Tests that a DirectionalLight lights every point from the same direction
+    fn directional_light() {
+        let light: Box<dyn Light> = Box::new(DirectionalLight::new(WHITE, Vec4::new(0.0, -2.0, 0.0, 0.0)));
+        let near = light.light_vector(&Vec4::new(0.0, 0.0, 0.0, 1.0), &light.get_positions()[0]);
+        let far = light.light_vector(&Vec4::new(100.0, -50.0, 3.0, 1.0), &light.get_positions()[0]);
+        assert_eq!(near, Vec4::new(0.0, 1.0, 0.0, 0.0));
+        assert_eq!(near, far);
+
+        let material = Material::default();
+        let point = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        let normal = Vec4::new(0.0, 1.0, 0.0, 0.0);
+        let color = lighting(&material, &Matrix4x4::identity(), &light, &point, &normal, &normal, &WHITE, &vec![]);
+        assert_eq!(color.round(), Color(1.9, 1.9, 1.9).round());
+    }
+
+    #[test]
+    //Tests that a DirectionalLight is blocked by objects at any distance along its direction
+    fn directional_light_shadow() {
+        let light = DirectionalLight::new(WHITE, Vec4::new(0.0, -1.0, 0.0, 0.0));
+        let point = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        let scene = occluded_scene(vec![Box::new(Sphere::new(Matrix4x4::translation(0.0, 1000.0, 0.0), Material::default()))]);
+        assert_eq!(light.light_intensity(&point, &scene), BLACK);
+        let scene = occluded_scene(vec![Box::new(Sphere::new(Matrix4x4::translation(0.0, -1000.0, 0.0), Material::default()))]);
+        assert_eq!(light.light_intensity(&point, &scene), WHITE);
+    }
+
+    #[test]
+    //Tests that attenuated lights are dimmer further away while ambient light is unchanged
+    fn attenuated_lighting() {
+        let material = Material::default();
+        let point = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        let normal = Vec4::new(0.0, 0.0, -1.0, 0.0);
+        let color_at = |distance: f32| {
+            let mut light = PointLight::new(WHITE, Vec4::new(0.0, 0.0, -distance, 1.0));
+            light.attenuation = Attenuation::InverseSquare;
+            let light: Box<dyn Light> = Box::new(light);
+            lighting(&material, &Matrix4x4::identity(), &light, &point, &normal, &normal, &WHITE, &vec![])
+        };
+        assert_eq!(color_at(1.0).round(), Color(1.9, 1.9, 1.9).round());
+        assert_eq!(color_at(2.0).round(), Color(0.55, 0.55, 0.55).round());
+        assert_eq!(color_at(500.0).round(), Color(0.1, 0.1, 0.1).round());
+    }
+
+    #[test]
+    //Tests that directional lights never weaken with distance
+    fn directional_light_attenuation() {
+        let light = DirectionalLight::new(WHITE, Vec4::new(0.0, -1.0, 0.0, 0.0));
+        assert_eq!(light.get_attenuation(), &Attenuation::None);
+        assert_eq!(light.attenuation(&Vec4::new(0.0, -1000.0, 0.0, 1.0), &light.get_positions()[0]), 1.0);
+    }
+
+    #[test]
+    //Tests creating Halton sequences
+    fn halton_sequence() {
+        let sequence = Sequence::halton(2, 4);
+        assert_eq!(sequence.contents, vec![0.5, 0.25, 0.75, 0.125]);
+        let sequence = Sequence::halton(3, 3);
+        assert_eq!(sequence.contents.iter().map(|x| (x * 9.0).round()).collect::<Vec<f32>>(), vec![3.0, 6.0, 1.0]);
+    }
+
+    #[test]
+    //Tests that each sampling strategy picks one point per cell inside the unit square
+    fn unit_square_sampling() {
+        for sampling in [LightSampling::Stratified, LightSampling::Jittered, LightSampling::LowDiscrepancy].iter() {
+            let samples = sample_unit_square(*sampling, 4, 2);
+            assert_eq!(samples.len(), 8);
+            assert!(samples.iter().all(|(u, v)| *u >= 0.0 && *u < 1.0 && *v >= 0.0 && *v < 1.0));
+        }
+        let samples = sample_unit_square(LightSampling::Stratified, 2, 2);
+        assert_eq!(samples, vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)]);
+        assert_eq!(sample_unit_square(LightSampling::LowDiscrepancy, 3, 3), sample_unit_square(LightSampling::LowDiscrepancy, 3, 3));
+    }
+
+    #[test]
+    //Tests that jittered area light samples stay within their cells
+    fn area_light_jitter() {
+        let light = AreaLight::new(Vec4::new(0.0, 0.0, 0.0, 1.0), Vec4::new(2.0, 0.0, 0.0, 0.0), 2, Vec4::new(0.0, 0.0, 1.0, 0.0), 1, WHITE);
+        for _ in 0..20 {
+            let point = light.point_on_light(1, 0, true);
+            assert!(point.0 >= 1.0 && point.0 <= 2.0 && point.2 >= 0.0 && point.2 <= 1.0);
+        }
+        assert_eq!(light.point_at(0.5, 0.5), Vec4::new(1.0, 0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    //Tests that points on a SphereLight are on its surface
+    fn sphere_light_points() {
+        let light = SphereLight::new(Vec4::new(1.0, 2.0, 3.0, 1.0), 2.0, 4, 4, WHITE);
+        for position in light.get_positions() {
+            assert!((Vec4::magnitude(&(position - &light.center)) - 2.0).abs() < 0.0001);
+        }
+        assert_eq!(light.get_positions().len(), 16);
+        assert_eq!(light.point_at(0.0, 0.0).round(), Vec4::new(1.0, 4.0, 3.0, 1.0).round());
+    }
+
+    #[test]
+    //Tests that points on a DiskLight are flat, inside its radius and that it only lights points in front of it
+    fn disk_light_points() {
+        let light = DiskLight::new(Vec4::new(0.0, 5.0, 0.0, 1.0), Vec4::new(0.0, -1.0, 0.0, 0.0), 2.0, 4, 4, WHITE);
+        for position in light.get_positions() {
+            assert!((position.1 - 5.0).abs() < 0.0001);
+            assert!(Vec4::magnitude(&(position - &light.center)) <= 2.0001);
+        }
+        let scene = occluded_scene(vec![]);
+        assert_eq!(light.light_intensity(&Vec4::new(0.0, 0.0, 0.0, 1.0), &scene), WHITE);
+        assert_eq!(light.light_intensity(&Vec4::new(0.0, 10.0, 0.0, 1.0), &scene), BLACK);
+    }
+
+    #[test]
+    //Tests that stratified and low discrepancy sampling give the same soft shadow every time
+    fn deterministic_soft_shadows() {
+        let scene = occluded_scene(vec![Box::new(Sphere::new(Matrix4x4::translation(1.0, 5.0, 0.0), Material::default()))]);
+        let point = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        for sampling in [LightSampling::Stratified, LightSampling::LowDiscrepancy].iter() {
+            let mut light = SphereLight::new(Vec4::new(0.0, 10.0, 0.0, 1.0), 3.0, 8, 8, WHITE);
+            light.sampling = *sampling;
+            let first = light.light_intensity(&point, &scene);
+            assert!(first.0 > 0.0 && first.0 < 1.0);
+            assert_eq!(light.light_intensity(&point, &scene), first);
+        }
+    }
+
+    #[test]
+    //Tests that visible lights can be seen by camera rays and reflections without blocking their own light
+    fn visible_light_geometry() {
+        let mut scene = Scene::new();
+        scene.add_light(Box::new(SphereLight::new(Vec4::new(0.0, 5.0, 0.0, 1.0), 1.0, 2, 2, Color::new(1.0, 0.8, 0.6))), true);
+        assert_eq!(scene.objects.len(), 1);
+        assert!(!scene.objects[0].get_material().casts_shadows);
+
+        //Seen directly
+        let color = Scene::compute_color(Ray::new((0.0, 0.0, 0.0), (0.0, 1.0, 0.0)), &scene, 5).unwrap();
+        assert_eq!(color, Color::new(1.0, 0.8, 0.6));
+
+        //Seen in a mirror
+        let mut mirror = Material::default();
+        mirror.color = BLACK;
+        mirror.ambient = 0.0;
+        mirror.diffuse = 0.0;
+        mirror.specular = 0.0;
+        mirror.reflectivity = 1.0;
+        scene.objects.push(Box::new(Plane::new(Matrix4x4::identity(), mirror)));
+        let color = Scene::compute_color(Ray::new((0.0, 1.0, -1.0), (0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2)), &scene, 5).unwrap();
+        assert_eq!(color, BLACK);
+        let color = Scene::compute_color(Ray::new((0.0, 1.0, 0.0), (0.0, -1.0, 0.0)), &scene, 5).unwrap();
+        assert_eq!(color.round(), Color::new(1.0, 0.8, 0.6).round());
+
+        //The light is not blocked by its own surface
+        let point = Vec4::new(3.0, 0.0, 0.0, 1.0);
+        assert_eq!(scene.light_sources[0].light_intensity(&point, &scene), WHITE);
+    }
+
+    #[test]
+    //Tests that lights are only given a surface when they have a size and are made visible
+    fn light_geometry() {
+        let mut scene = Scene::new();
+        scene.add_light(Box::new(PointLight::new(WHITE, Vec4::new(0.0, 5.0, 0.0, 1.0))), true);
+        scene.add_light(Box::new(SphereLight::new(Vec4::new(0.0, 5.0, 0.0, 1.0), 1.0, 2, 2, WHITE)), false);
+        assert_eq!(scene.objects.len(), 0);
+        assert_eq!(scene.light_sources.len(), 2);
+
+        //Area and disk lights are hit inside their outline and missed outside it
+        let area = AreaLight::new(Vec4::new(-1.0, 5.0, -1.0, 1.0), Vec4::new(2.0, 0.0, 0.0, 0.0), 2, Vec4::new(0.0, 0.0, 2.0, 0.0), 2, WHITE);
+        let disk = DiskLight::new(Vec4::new(0.0, 5.0, 0.0, 1.0), Vec4::new(0.0, -1.0, 0.0, 0.0), 1.0, 2, 2, WHITE);
+        for light in [area.geometry().unwrap(), disk.geometry().unwrap()].iter() {
+            let hit = light.intersect(&Ray::new((0.5, 0.0, 0.5), (0.0, 1.0, 0.0))).unwrap_or_default();
+            assert!(hit.iter().any(|i| (i.t - 5.0).abs() < 0.0001));
+            let miss = light.intersect(&Ray::new((1.5, 0.0, 1.5), (0.0, 1.0, 0.0))).unwrap_or_default();
+            assert!(miss.is_empty());
+        }
+
+        //The disk only shines downwards, so it can not be seen from above
+        let geometry = disk.geometry().unwrap();
+        assert!(geometry.intersect(&Ray::new((0.5, 10.0, 0.5), (0.0, -1.0, 0.0))).unwrap_or_default().is_empty());
     }
 }
@@ -126,7 +126,9 @@ mod tests {
         assert_eq!(Some(matrix3), matrix4);
         let matrix5 = Matrix3x3::identity();
         let matrix6 = matrix5.clone().inverse();
-        assert_eq!(Some(matrix5), matrix6)
+        assert_eq!(Some(matrix5), matrix6);
+        assert_eq!(Matrix4x4::scaling(-2.0, 1.0, 1.0).inverse(), Some(Matrix4x4::scaling(-0.5, 1.0, 1.0)));
+        assert_eq!(Matrix4x4::scaling(0.0, 1.0, 1.0).inverse(), None);
     }
 
     #[test]
@@ -246,4 +248,14 @@ mod tests {
         );
         assert_eq!(transform.round(), expected_result.round());
     }
+
+    //Tests blending between two matrices
+    #[test]
+    fn lerp() {
+        let start = Matrix4x4::translation(0.0, 0.0, 0.0);
+        let end = Matrix4x4::translation(2.0, 4.0, 0.0) * Matrix4x4::scaling(3.0, 3.0, 3.0);
+        assert_eq!(start.lerp(&end, 0.0), start);
+        assert_eq!(start.lerp(&end, 1.0), end);
+        assert_eq!(start.lerp(&end, 0.5), Matrix4x4::translation(1.0, 2.0, 0.0) * Matrix4x4::scaling(2.0, 2.0, 2.0));
+    }
 }
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::core::matrix::Matrix4x4;
+    use rust_ray_tracer::core::vector::Vec4;
+    use rust_ray_tracer::misc::axis::Axis;
+    use rust_ray_tracer::objects::bounds::BoundingBox;
+    use rust_ray_tracer::objects::motion::*;
+
+    //Tests splitting transforms into poses and building them again
+    #[test]
+    fn pose_round_trip() {
+        let transform = Matrix4x4::translation(1.0, -2.0, 3.0)
+            * Matrix4x4::rotation(Axis::Y, 130.0)
+            * Matrix4x4::rotation(Axis::X, -40.0)
+            * Matrix4x4::scaling(2.0, 0.5, -1.5);
+        let pose = Pose::from_matrix(&transform).unwrap();
+        assert_eq!(pose.translation, (1.0, -2.0, 3.0));
+        assert_eq!(pose.matrix().round(), transform.round());
+        let turned = Matrix4x4::rotation(Axis::Z, 180.0);
+        assert_eq!(Pose::from_matrix(&turned).unwrap().matrix().round(), turned.round());
+        assert!(Pose::from_matrix(&Matrix4x4::shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0)).is_none());
+        assert!(Pose::from_matrix(&(Matrix4x4::scaling(2.0, 1.0, 1.0) * Matrix4x4::rotation(Axis::Z, 45.0))).is_none());
+        assert!(Pose::from_matrix(&Matrix4x4::scaling(0.0, 1.0, 1.0)).is_none());
+    }
+
+    //Tests that turning objects keep their shape while their origin moves in a straight line
+    #[test]
+    fn turning_motion() {
+        let motion = Motion::new(Matrix4x4::rotation(Axis::Y, 120.0));
+        let start = Matrix4x4::identity();
+        assert_eq!(motion.transform_at(&start, 0.5).round(), Matrix4x4::rotation(Axis::Y, 60.0).round());
+        assert_eq!(motion.transform_at(&start, 1.0).round(), Matrix4x4::rotation(Axis::Y, 120.0).round());
+        let half_turn = Motion::new(Matrix4x4::rotation(Axis::Y, 180.0)).transform_at(&start, 0.5);
+        assert!((Vec4::magnitude(&(half_turn * Vec4::new(1.0, 0.0, 0.0, 0.0))) - 1.0).abs() < 0.0001);
+
+        let start = Matrix4x4::translation(2.0, 0.0, 0.0);
+        let motion = Motion::new(Matrix4x4::rotation(Axis::Y, 90.0) * Matrix4x4::translation(2.0, 0.0, 0.0) * Matrix4x4::scaling(2.0, 2.0, 2.0));
+        let middle = motion.transform_at(&start, 0.5) * Vec4::new(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(middle.round(), Vec4::new(1.0, 0.0, -1.0, 1.0));
+        let scaled = motion.transform_at(&start, 0.5) * Vec4::new(1.0, 0.0, 0.0, 0.0);
+        assert!((Vec4::magnitude(&scaled) - 1.5).abs() < 0.0001);
+
+        //The bounds cover the object halfway through its turn, which is outside both of its ends
+        let local = BoundingBox::new(Vec4(-1.0, -1.0, -1.0, 1.0), Vec4(1.0, 1.0, 1.0, 1.0));
+        let bounds = Motion::bounds(&Some(motion.clone()), &start, &local);
+        assert!(bounds.contains_box(&local.transform(&motion.transform_at(&start, 0.5))));
+        assert!(bounds.contains_box(&local.transform(&motion.end)));
+    }
+
+    //Tests that objects which flatten part way through their motion are seen where they start
+    #[test]
+    fn flattened_motion() {
+        let start = Matrix4x4::identity();
+        let motion = Motion::new(Matrix4x4::scaling(-1.0, 1.0, 1.0));
+        assert!(motion.transform_at(&start, 0.5).inverse().is_none());
+        assert_eq!(Motion::rewind(&Some(motion.clone()), &start, 0.5), None);
+        assert!(Motion::rewind(&Some(motion.clone()), &start, 0.25).is_some());
+        assert!(!motion.is_invertible(&start));
+        assert!(Motion::new(Matrix4x4::scaling(2.0, 0.5, 1.0)).is_invertible(&start));
+    }
+}
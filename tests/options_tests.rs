@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::misc::options::*;
+    use rust_ray_tracer::world::antialiasing::*;
+    use rust_ray_tracer::world::camera::Camera;
+    use rust_ray_tracer::world::settings::*;
+    use rust_ray_tracer::world::stereo::*;
+
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    //Tests reading every option
+    fn parse_options() {
+        let options = Options::parse(&args("scene.yml -o out.ppm --width 640 --height 480 --fov 60 -m supersampled -d 3 -s 9 -t 2 -q")).unwrap();
+        assert_eq!(options.scene, "scene.yml");
+        assert_eq!(options.output, "out.ppm");
+        assert_eq!(options.width, Some(640));
+        assert_eq!(options.height, Some(480));
+        assert_eq!(options.fov, Some(60.0));
+        assert_eq!(options.mode, RenderMode::Supersampled);
+        assert_eq!(options.depth, Some(3));
+        assert_eq!(options.samples, Some(9));
+        assert_eq!(options.threads, Some(2));
+        assert!(options.quiet);
+
+        let options = Options::parse(&args("scene.yml -i path")).unwrap();
+        assert_eq!(options.integrator, Some(Integrator::PathTracer));
+        assert!(Options::parse(&args("scene.yml -i photon")).is_err());
+
+        let options = Options::parse(&args("scene.yml --sampler jittered --filter mitchell")).unwrap();
+        assert_eq!(options.sampler, Some(Sampler::Jittered));
+        assert_eq!(options.filter, Some(Filter::Mitchell));
+        assert!(Options::parse(&args("scene.yml --sampler sobol")).is_err());
+        assert!(Options::parse(&args("scene.yml --filter lanczos")).is_err());
+
+        let options = Options::parse(&args("scene.yml -m adaptive --threshold 0.05 --adaptive-depth 3 --heatmap heat.ppm")).unwrap();
+        assert_eq!(options.mode, RenderMode::Adaptive);
+        assert_eq!(options.threshold, Some(0.05));
+        assert_eq!(options.adaptive_depth, Some(3));
+        assert_eq!(options.heatmap, Some(String::from("heat.ppm")));
+
+        let options = Options::parse(&args("scene.yml --frames 0-47")).unwrap();
+        assert_eq!(options.frames, Some((0, 47)));
+        let options = Options::parse(&args("scene.yml --frames 12")).unwrap();
+        assert_eq!(options.frames, Some((12, 12)));
+
+        let options = Options::parse(&args("scene.yml --stereo top-bottom --interocular 0.1 --convergence 4 --toe-in")).unwrap();
+        assert_eq!(options.stereo, Some(Layout::TopBottom));
+        assert_eq!(options.interocular, Some(0.1));
+        assert_eq!(options.convergence, Some(4.0));
+        assert!(options.toe_in);
+
+        let options = Options::parse(&args("scene.yml")).unwrap();
+        assert_eq!(options.output, "image.ppm");
+        assert_eq!(options.frames, None);
+        assert_eq!(options.integrator, None);
+        assert_eq!(options.mode, RenderMode::Render);
+        assert_eq!(options.width, None);
+    }
+
+    #[test]
+    //Tests rejecting bad arguments
+    fn parse_errors() {
+        assert!(Options::parse(&args("")).is_err());
+        assert!(Options::parse(&args("scene.yml --width")).is_err());
+        assert!(Options::parse(&args("scene.yml --width wide")).is_err());
+        assert!(Options::parse(&args("scene.yml -m fast")).is_err());
+        assert!(Options::parse(&args("scene.yml --colour red")).is_err());
+        assert!(Options::parse(&args("scene.yml other.yml")).is_err());
+        assert!(Options::parse(&args("scene.yml --shadow-floor 1.5")).is_err());
+        assert!(Options::parse(&args("scene.yml --seed -1")).is_err());
+        assert!(Options::parse(&args("scene.yml -d -1")).is_err());
+        assert!(Options::parse(&args("scene.yml --depth 1000000")).is_err());
+        assert!(Options::parse(&args("scene.yml --depth 0")).is_ok());
+        assert!(Options::parse(&args("scene.yml -m adaptive --threshold -0.1")).is_err());
+        assert!(Options::parse(&args("scene.yml --heatmap heat.ppm")).is_err());
+        assert!(Options::parse(&args("scene.yml --frames 10-2")).is_err());
+        assert!(Options::parse(&args("scene.yml --frames 0-")).is_err());
+        assert!(Options::parse(&args("scene.yml --frames all")).is_err());
+        assert!(Options::parse(&args("scene.yml --stereo anaglyph")).is_err());
+        assert!(Options::parse(&args("scene.yml --toe-in")).is_err());
+        assert!(Options::parse(&args("scene.yml --stereo side-by-side --convergence 0")).is_err());
+        assert!(Options::parse(&args("scene.yml --stereo side-by-side --interocular -1")).is_err());
+        assert!(Options::parse(&args("--help")).unwrap().help);
+    }
+
+    #[test]
+    //Tests creating a stereo rig around the scene's camera
+    fn stereo_rig() {
+        let mut camera = Camera::new(200, 100, 90.0);
+        camera.focal_distance = 6.0;
+        assert!(Options::parse(&args("scene.yml")).unwrap().stereo_rig(&camera).is_none());
+        let rig = Options::parse(&args("scene.yml --stereo side-by-side")).unwrap().stereo_rig(&camera).unwrap();
+        assert_eq!(rig.layout, Layout::SideBySide);
+        assert_eq!(rig.convergence, Convergence::OffAxis);
+        assert_eq!(rig.convergence_distance, 6.0);
+        assert_eq!(rig.interocular, 0.2);
+        let rig = Options::parse(&args("scene.yml --stereo top-bottom --interocular 0.5 --toe-in")).unwrap().stereo_rig(&camera).unwrap();
+        assert_eq!(rig.convergence, Convergence::ToeIn);
+        assert_eq!(rig.interocular, 0.5);
+        assert_eq!(rig.size(), (200, 200));
+    }
+
+    #[test]
+    //Tests numbering the files frames of an animation are written to
+    fn frame_paths() {
+        assert_eq!(Options::frame_path("image.ppm", 7), "image_0007.ppm");
+        assert_eq!(Options::frame_path("frames/shot_###.png", 7), "frames/shot_007.png");
+        assert_eq!(Options::frame_path("frames/#.png", 12), "frames/12.png");
+        assert_eq!(Options::frame_path("out.v2/image", 3), "out.v2/image_0003");
+    }
+
+    #[test]
+    //Tests overriding camera settings while keeping the aspect ratio
+    fn apply_options() {
+        let mut camera = Camera::new(200, 100, 90.0);
+        let mut settings = RenderSettings::default();
+        let options = Options::parse(&args("scene.yml --width 50 -d 2 -s 4 --shadow-floor 0.25 --opaque-shadows --seed 42")).unwrap();
+        options.apply(&mut camera, &mut settings);
+        assert_eq!(camera.hsize, 50);
+        assert_eq!(camera.vsize, 25);
+        assert!((camera.field_of_view() - 90.0).abs() < 0.001);
+        assert_eq!(settings.max_depth, 2);
+        assert_eq!(settings.samples, 4);
+        assert_eq!(settings.shadow_floor, 0.25);
+        assert!(!settings.transparent_shadows);
+        assert_eq!(settings.seed, 42);
+        assert!(settings.show_progress);
+    }
+}
@@ -8,7 +8,7 @@ mod tests {
     //Tests obj vertex parsing
     fn obj_vertex_parsing() {
         let file = File::open("tests/test1.obj");
-        let result = Parser::parse_obj(file.unwrap());
+        let result = Parser::parse_obj(file.unwrap()).unwrap();
         assert_eq!(result.vertices.len(), 4);
     }
 
@@ -16,7 +16,7 @@ mod tests {
     //Tests obj triangle parsing
     fn obj_triangle_parsing() {
         let file = File::open("tests/test1.obj");
-        let result = Parser::parse_obj(file.unwrap());
+        let result = Parser::parse_obj(file.unwrap()).unwrap();
         assert_eq!(&result.triangles.len(), &2);
         let t1 = result.triangles[0].clone();
         let t2 = result.triangles[1].clone();
@@ -32,7 +32,7 @@ mod tests {
     //Tests obj polygon parsing
     fn obj_polygon_parsing() {
         let file = File::open("tests/test2.obj");
-        let result = Parser::parse_obj(file.unwrap());
+        let result = Parser::parse_obj(file.unwrap()).unwrap();
         assert_eq!(&result.triangles.len(), &3);
         let t1 = result.triangles[0].clone();
         let t2 = result.triangles[1].clone();
@@ -51,7 +51,7 @@ mod tests {
     #[test]
     fn smooth_obj_parsing() {
         let file = File::open("tests/test3.obj");
-        let result = Parser::parse_obj(file.unwrap());
+        let result = Parser::parse_obj(file.unwrap()).unwrap();
         assert_eq!(&result.smooth_triangles.len(), &2);
         let t1 = result.smooth_triangles[0].clone();
         let t2 = result.smooth_triangles[1].clone();
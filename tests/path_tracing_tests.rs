@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::world::path_tracing::*;
+    use rust_ray_tracer::world::lighting::SphereLight;
+    use rust_ray_tracer::world::background::Background;
+    use rust_ray_tracer::world::scene::Scene;
+    use rust_ray_tracer::world::settings::Integrator;
+    use rust_ray_tracer::world::camera::Camera;
+    use rust_ray_tracer::objects::sphere::Sphere;
+    use rust_ray_tracer::objects::plane::Plane;
+    use rust_ray_tracer::core::canvas::Canvas;
+    use rust_ray_tracer::core::color::*;
+    use rust_ray_tracer::core::comp::Comp;
+    use rust_ray_tracer::core::matrix::Matrix4x4;
+    use rust_ray_tracer::core::vector::Vec4;
+    use rust_ray_tracer::materials::material::Material;
+    use rust_ray_tracer::ray_tracing::intersection::Intersection;
+    use rust_ray_tracer::ray_tracing::ray::Ray;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    //Creates a material which only has the given lobe weights
+    fn material(diffuse: f32, specular: f32, reflectivity: f32) -> Material {
+        let mut material = Material::default();
+        material.diffuse = diffuse;
+        material.specular = specular;
+        material.reflectivity = reflectivity;
+        material
+    }
+
+    #[test]
+    //Tests that escaping paths see the background and emissive objects give off light
+    fn background_and_emission() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut scene = Scene::new();
+        scene.background = Background::Color(Color::new(0.2, 0.4, 0.6));
+        assert_eq!(path_trace(Ray::new((0.0, 0.0, -5.0), (0.0, 0.0, 1.0)), &scene, &mut rng), Color::new(0.2, 0.4, 0.6));
+
+        let mut glowing = material(0.0, 0.0, 0.0);
+        glowing.emission = Color::new(2.0, 1.0, 0.5);
+        scene.objects.push(Box::new(Sphere::new(Matrix4x4::identity(), glowing)));
+        assert_eq!(path_trace(Ray::new((0.0, 0.0, -5.0), (0.0, 0.0, 1.0)), &scene, &mut rng), Color::new(2.0, 1.0, 0.5));
+    }
+
+    #[test]
+    //Tests that a diffuse floor under a white sky reflects its albedo
+    fn diffuse_under_sky() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut scene = Scene::new();
+        scene.background = Background::Color(WHITE);
+        let mut floor = material(0.5, 0.0, 0.0);
+        floor.color = Color::new(1.0, 0.5, 1.0);
+        scene.objects.push(Box::new(Plane::new(Matrix4x4::identity(), floor)));
+        for _ in 0..20 {
+            let color = path_trace(Ray::new((0.0, 1.0, -1.0), (0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2)), &scene, &mut rng);
+            assert_eq!(color.round(), Color::new(0.5, 0.25, 0.5));
+        }
+    }
+
+    #[test]
+    //Tests picking lobes in proportion to material weights
+    fn lobe_choice() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut scene = Scene::new();
+        scene.objects.push(Box::new(Sphere::new(Matrix4x4::identity(), material(0.0, 0.0, 0.8))));
+        let ray = Ray::new((0.0, 0.0, -5.0), (0.0, 0.0, 1.0));
+        let intersections = Ray::intersect_scene(&scene, ray.clone());
+        let comps = Comp::compute_vars(Intersection::hit(&intersections).unwrap(), &ray, &intersections);
+        assert_eq!(choose_lobe(&comps, &mut rng), Some((Lobe::Mirror, 0.8)));
+
+        scene.objects[0] = Box::new(Sphere::new(Matrix4x4::identity(), material(1.0, 1.0, 0.0)));
+        let intersections = Ray::intersect_scene(&scene, ray.clone());
+        let comps = Comp::compute_vars(Intersection::hit(&intersections).unwrap(), &ray, &intersections);
+        let mut diffuse = 0;
+        for _ in 0..1000 {
+            let (lobe, weight) = choose_lobe(&comps, &mut rng).unwrap();
+            assert_eq!(weight, 1.0);
+            if lobe == Lobe::Diffuse {
+                diffuse += 1;
+            }
+        }
+        assert!(diffuse > 400 && diffuse < 600);
+    }
+
+    #[test]
+    //Tests that sampled directions stay on the correct side of the surface
+    fn sampled_directions() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let normal = Vec4::new(0.0, 0.0, 1.0, 0.0);
+        for _ in 0..100 {
+            let direction = sample_cosine_hemisphere(&normal, &mut rng);
+            assert!(Vec4::dot(&direction, &normal) >= 0.0);
+            assert!((Vec4::magnitude(&direction) - 1.0).abs() < 0.001);
+            let glossy = sample_phong_lobe(&normal, 1000.0, &mut rng);
+            assert!(Vec4::dot(&glossy, &normal) > 0.99);
+        }
+    }
+
+    #[test]
+    //Tests rendering with the path tracer selected
+    fn path_traced_render() {
+        let mut scene = Scene::new();
+        scene.background = Background::Color(Color::new(0.5, 0.5, 0.5));
+        scene.settings.integrator = Integrator::PathTracer;
+        scene.settings.samples = 2;
+        let camera = Camera::new(8, 4, 90.0);
+        let mut canvas = Canvas::new(8, 4);
+        Camera::render(&camera, &scene, &mut canvas);
+        assert_eq!(canvas.get(3, 2), Some(&Color::new(0.5, 0.5, 0.5)));
+    }
+
+    #[test]
+    //Tests that visible lights are seen by the camera but are not counted twice when lighting diffuse surfaces
+    fn visible_light_not_counted_twice() {
+        let mut hidden = Scene::new();
+        hidden.add_light(Box::new(SphereLight::new(Vec4::new(0.0, 3.0, 0.0, 1.0), 1.0, 2, 2, WHITE)), false);
+        hidden.objects.push(Box::new(Plane::new(Matrix4x4::identity(), material(0.9, 0.0, 0.0))));
+        let mut visible = Scene::new();
+        visible.add_light(Box::new(SphereLight::new(Vec4::new(0.0, 3.0, 0.0, 1.0), 1.0, 2, 2, WHITE)), true);
+        visible.objects.push(Box::new(Plane::new(Matrix4x4::identity(), material(0.9, 0.0, 0.0))));
+
+        let mut rng = StdRng::seed_from_u64(4);
+        assert_eq!(path_trace(Ray::new((0.0, 0.0, 0.0), (0.0, 1.0, 0.0)), &visible, &mut rng), WHITE);
+
+        let ray = Ray::new((0.0, 1.0, -1.0), (0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let average = |scene: &Scene, seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut total = BLACK;
+            for _ in 0..2000 {
+                total = total + path_trace(ray.clone(), scene, &mut rng);
+            }
+            total * (1.0 / 2000.0)
+        };
+        let difference = (average(&visible, 5).0 - average(&hidden, 5).0).abs();
+        assert!(difference < 0.02, "difference was {}", difference);
+    }
+}
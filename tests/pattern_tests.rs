@@ -5,6 +5,9 @@ mod tests {
     use rust_ray_tracer::core::color::*;
     use rust_ray_tracer::core::vector::Vec4;
     use rust_ray_tracer::core::matrix::Matrix4x4;
+    use rust_ray_tracer::core::canvas::Canvas;
+    use std::sync::Arc;
+    use std::f32::consts::FRAC_1_SQRT_2;
     
     //Tests if the  stripe pattern is constant for z
     #[test]
@@ -79,4 +82,99 @@ mod tests {
         assert_eq!(pattern.color_at(&Vec4::new(0.0, 0.0, 0.99, 1.0)), WHITE);
         assert_eq!(pattern.color_at(&Vec4::new(0.0, 0.0, 1.01, 1.0)), BLACK);
     }
-}
\ No newline at end of file
+
+    //Rounds (u, v) coordinates for testing
+    fn round_uv((u, v): (f32, f32)) -> (f32, f32) {
+        ((u * 10000.0).round() / 10000.0, (v * 10000.0).round() / 10000.0)
+    }
+
+    #[test]
+    //Tests the uv coordinates of points on a sphere
+    fn spherical_mapping() {
+        assert_eq!(round_uv(spherical_map(&Vec4::new(0.0, 0.0, -1.0, 1.0))), (0.0, 0.5));
+        assert_eq!(round_uv(spherical_map(&Vec4::new(1.0, 0.0, 0.0, 1.0))), (0.25, 0.5));
+        assert_eq!(round_uv(spherical_map(&Vec4::new(0.0, 0.0, 1.0, 1.0))), (0.5, 0.5));
+        assert_eq!(round_uv(spherical_map(&Vec4::new(-1.0, 0.0, 0.0, 1.0))), (0.75, 0.5));
+        assert_eq!(round_uv(spherical_map(&Vec4::new(0.0, 1.0, 0.0, 1.0))), (0.5, 1.0));
+        assert_eq!(round_uv(spherical_map(&Vec4::new(0.0, -1.0, 0.0, 1.0))), (0.5, 0.0));
+        assert_eq!(round_uv(spherical_map(&Vec4::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0, 1.0))), (0.25, 0.75));
+    }
+
+    #[test]
+    //Tests the uv coordinates of points on planes and cylinders
+    fn planar_and_cylindrical_mapping() {
+        assert_eq!(round_uv(planar_map(&Vec4::new(0.25, 0.0, 0.5, 1.0))), (0.25, 0.5));
+        assert_eq!(round_uv(planar_map(&Vec4::new(-0.25, 0.0, -0.5, 1.0))), (0.75, 0.5));
+        assert_eq!(round_uv(planar_map(&Vec4::new(1.25, 0.5, -1.75, 1.0))), (0.25, 0.25));
+        assert_eq!(round_uv(cylindrical_map(&Vec4::new(0.0, 0.0, -1.0, 1.0))), (0.0, 0.0));
+        assert_eq!(round_uv(cylindrical_map(&Vec4::new(0.0, 0.5, -1.0, 1.0))), (0.0, 0.5));
+        assert_eq!(round_uv(cylindrical_map(&Vec4::new(FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2, 1.0))), (0.125, 0.5));
+        assert_eq!(round_uv(cylindrical_map(&Vec4::new(-FRAC_1_SQRT_2, -0.25, -FRAC_1_SQRT_2, 1.0))), (0.875, 0.75));
+    }
+
+    #[test]
+    //Tests finding the face and uv coordinates of points on a cube
+    fn cube_mapping() {
+        assert_eq!(cube_face(&Vec4::new(-1.0, 0.5, -0.25, 1.0)), CubeFace::Left);
+        assert_eq!(cube_face(&Vec4::new(1.1, -0.75, 0.8, 1.0)), CubeFace::Right);
+        assert_eq!(cube_face(&Vec4::new(0.1, 0.6, 0.9, 1.0)), CubeFace::Front);
+        assert_eq!(cube_face(&Vec4::new(-0.7, 0.0, -2.0, 1.0)), CubeFace::Back);
+        assert_eq!(cube_face(&Vec4::new(0.5, 1.0, 0.9, 1.0)), CubeFace::Up);
+        assert_eq!(cube_face(&Vec4::new(-0.2, -1.3, 1.1, 1.0)), CubeFace::Down);
+        assert_eq!(cube_map(&Vec4::new(-0.5, 0.5, 1.0, 1.0)), (CubeFace::Front, 0.25, 0.75));
+        assert_eq!(cube_map(&Vec4::new(0.5, -0.5, 1.0, 1.0)), (CubeFace::Front, 0.75, 0.25));
+        assert_eq!(cube_map(&Vec4::new(-1.0, 0.5, -0.5, 1.0)), (CubeFace::Left, 0.25, 0.75));
+        assert_eq!(cube_map(&Vec4::new(-0.5, 1.0, -0.5, 1.0)), (CubeFace::Up, 0.25, 0.75));
+
+        //Points on an edge, pushed just past it by rounding, stay on the edge instead of wrapping to the other side
+        assert_eq!(cube_map(&Vec4::new(1.0, 1.0, 1.0001, 1.0)), (CubeFace::Front, 1.0, 1.0));
+        assert_eq!(cube_map(&Vec4::new(-1.0, 0.0, -1.0001, 1.0)), (CubeFace::Back, 1.0, 0.5));
+    }
+
+    #[test]
+    //Tests reading texture colors with nearest and bilinear filtering
+    fn texture_filtering() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.set(BLACK, 0, 0);
+        canvas.set(WHITE, 1, 0);
+        canvas.set(WHITE, 0, 1);
+        canvas.set(BLACK, 1, 1);
+        let canvas = Arc::new(canvas);
+
+        let nearest = TexturePattern::new(canvas.clone(), UvMapping::Planar, TextureFilter::Nearest, Matrix4x4::identity());
+        assert_eq!(nearest.color_at(&Vec4::new(0.2, 0.0, 0.9, 1.0)), BLACK);
+        assert_eq!(nearest.color_at(&Vec4::new(0.7, 0.0, 0.9, 1.0)), WHITE);
+        assert_eq!(nearest.color_at(&Vec4::new(0.2, 0.0, 0.1, 1.0)), WHITE);
+
+        let bilinear = TexturePattern::new(canvas, UvMapping::Planar, TextureFilter::Bilinear, Matrix4x4::identity());
+        assert_eq!(bilinear.color_at(&Vec4::new(0.5, 0.0, 0.5, 1.0)), Color::new(0.5, 0.5, 0.5));
+        assert_eq!(bilinear.color_at(&Vec4::new(0.25, 0.0, 0.75, 1.0)), BLACK);
+        assert_eq!(bilinear.color_at(&Vec4::new(0.5, 0.0, 0.75, 1.0)), Color::new(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    //Tests reading each face of a cross shaped cube texture
+    fn texture_cube_faces() {
+        let mut canvas = Canvas::new(8, 6);
+        let faces = [
+            (CubeFace::Up, Vec4::new(0.0, 1.0, 0.0, 1.0)),
+            (CubeFace::Down, Vec4::new(0.0, -1.0, 0.0, 1.0)),
+            (CubeFace::Left, Vec4::new(-1.0, 0.0, 0.0, 1.0)),
+            (CubeFace::Right, Vec4::new(1.0, 0.0, 0.0, 1.0)),
+            (CubeFace::Front, Vec4::new(0.0, 0.0, 1.0, 1.0)),
+            (CubeFace::Back, Vec4::new(0.0, 0.0, -1.0, 1.0)),
+        ];
+        for (i, (face, _)) in faces.iter().enumerate() {
+            let (column, row) = cube_face_cell(*face);
+            for y in 0..2 {
+                for x in 0..2 {
+                    canvas.set(Color::new(i as f32 / 10.0, 0.0, 0.0), (column * 2 + x) as i32, (row * 2 + y) as i32);
+                }
+            }
+        }
+        let pattern = TexturePattern::new(Arc::new(canvas), UvMapping::Cube, TextureFilter::Bilinear, Matrix4x4::identity());
+        for (i, (_, point)) in faces.iter().enumerate() {
+            assert_eq!(pattern.color_at(point), Color::new(i as f32 / 10.0, 0.0, 0.0));
+        }
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::core::png::*;
+
+    //Tests the checksums against known values
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    //Tests that repeated data is compressed
+    #[test]
+    fn deflate_repeats() {
+        let data = vec![7; 10000];
+        let compressed = zlib_compress(&data);
+        assert!(compressed.len() < 200);
+        assert_eq!(&compressed[..2], &[0x78, 0x01]);
+        assert_eq!(&compressed[(compressed.len() - 4)..], &adler32(&data).to_be_bytes());
+    }
+
+    //Tests the paeth predictor
+    #[test]
+    fn paeth_predictor() {
+        assert_eq!(predict(4, 10, 20, 10), 20);
+        assert_eq!(predict(4, 20, 10, 10), 20);
+        assert_eq!(predict(4, 10, 10, 20), 10);
+        assert_eq!(predict(3, 255, 255, 0), 255);
+    }
+
+    //Tests that compressed data can be decompressed
+    #[test]
+    fn inflate_round_trip() {
+        let data: Vec<u8> = (0..5000).map(|i| ((i * 7) % 13 + i / 500) as u8).collect();
+        assert_eq!(zlib_decompress(&zlib_compress(&data)).unwrap(), data);
+        assert_eq!(zlib_decompress(&zlib_compress(&[])).unwrap(), Vec::<u8>::new());
+    }
+
+    //Tests reading stored deflate blocks and rejecting bad checksums
+    #[test]
+    fn inflate_stored() {
+        //zlib stream holding a single stored block containing "abc"
+        let mut stream = vec![0x78, 0x01, 0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'];
+        stream.extend_from_slice(&adler32(b"abc").to_be_bytes());
+        assert_eq!(zlib_decompress(&stream).unwrap(), b"abc");
+        let length = stream.len();
+        stream[length - 1] ^= 1;
+        assert!(zlib_decompress(&stream).is_err());
+    }
+
+    //Tests that headers with sizes which are zero or too large to store are rejected
+    #[test]
+    fn decode_bad_size() {
+        assert_eq!(decode(&encode(2, 1, &[0; 6])).unwrap(), (2, 1, vec![0; 6]));
+        for (width, height) in [(0, 1), (1, 0), (0, 0), (u32::MAX, u32::MAX)].iter() {
+            let mut bytes = encode(1, 1, &[0; 3]);
+            bytes[16..20].copy_from_slice(&width.to_be_bytes());
+            bytes[20..24].copy_from_slice(&height.to_be_bytes());
+            let crc = crc32(&bytes[12..29]);
+            bytes[29..33].copy_from_slice(&crc.to_be_bytes());
+            assert_eq!(decode(&bytes).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+        }
+    }
+}
@@ -0,0 +1,496 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::world::scene_file::SceneFile;
+    use rust_ray_tracer::world::camera::*;
+    use rust_ray_tracer::misc::yaml::*;
+    use rust_ray_tracer::objects::sphere::Sphere;
+    use rust_ray_tracer::objects::group::Group;
+    use rust_ray_tracer::core::color::Color;
+    use rust_ray_tracer::core::matrix::Matrix4x4;
+    use rust_ray_tracer::core::vector::Vec4;
+    use rust_ray_tracer::ray_tracing::ray::Ray;
+    use rust_ray_tracer::world::attenuation::Attenuation;
+    use rust_ray_tracer::world::background::Background;
+    use std::path::Path;
+
+    const CAMERA: &str = "
+- add: camera
+  width: 100
+  height: 50
+  field-of-view: 1.5708
+  from: [0, 0, -5]
+  to: [0, 0, 0]
+  up: [0, 1, 0]
+";
+
+    #[test]
+    //Tests parsing nested maps, lists and inline values
+    fn parse_yaml() {
+        let node = Node::parse("
+# comment
+- add: sphere   # trailing comment
+  transform:
+    - [scale, 1, 2, 3]
+  material: { color: [1, 0.5, 0], shadow: false }
+- name: \"quoted # text\"
+").unwrap();
+        let items = node.as_list().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].get("add").unwrap().as_str().unwrap(), "sphere");
+        assert_eq!(items[0].line, 3);
+        let transform = items[0].get("transform").unwrap().as_list().unwrap();
+        assert_eq!(transform[0].as_list().unwrap()[3].as_f32().unwrap(), 3.0);
+        let material = items[0].get("material").unwrap();
+        assert_eq!(material.get("color").unwrap().as_triple().unwrap(), (1.0, 0.5, 0.0));
+        assert!(!material.get("shadow").unwrap().as_bool().unwrap());
+        assert_eq!(items[1].get("name").unwrap().as_str().unwrap(), "quoted # text");
+    }
+
+    #[test]
+    //Tests loading a camera with a lens
+    fn load_lens() {
+        let text = CAMERA.replace("up: [0, 1, 0]", "up: [0, 1, 0]\n  aperture: 0.2\n  aperture-blades: 6");
+        let (_, camera) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(camera.aperture, 0.2);
+        assert_eq!(camera.focal_distance, 5.0);
+        assert_eq!(camera.aperture_shape, ApertureShape::Polygon(6));
+
+        let text = CAMERA.replace("up: [0, 1, 0]", "up: [0, 1, 0]\n  aperture: 0.2\n  focal-distance: 3");
+        let (_, camera) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(camera.focal_distance, 3.0);
+        assert_eq!(camera.aperture_shape, ApertureShape::Disk);
+    }
+
+    #[test]
+    //Tests loading a moving camera and a moving sphere
+    fn load_motion() {
+        let text = format!("{}
+- add: sphere
+  end-transform:
+    - [translate, 1, 0, 0]
+- add: plane
+", CAMERA.replace("up: [0, 1, 0]", "up: [0, 1, 0]\n  shutter-close: 0.5\n  end-from: [0, 1, -5]\n  end-to: [0, 1, 0]"));
+        let (scene, camera) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(camera.shutter_open, 0.0);
+        assert_eq!(camera.shutter_close, 0.5);
+        assert_eq!(camera.motion.as_ref().unwrap().end, Matrix4x4::view_transform(
+            Vec4::new(0.0, 1.0, -5.0, 1.0),
+            Vec4::new(0.0, 1.0, 0.0, 1.0),
+            Vec4::new(0.0, 1.0, 0.0, 0.0),
+        ));
+        let sphere = scene.objects[0].as_any().downcast_ref::<Sphere>().unwrap();
+        assert_eq!(sphere.motion.as_ref().unwrap().end, Matrix4x4::translation(1.0, 0.0, 0.0));
+
+        let text = CAMERA.replace("up: [0, 1, 0]", "up: [0, 1, 0]\n  shutter-close: 2");
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+        let text = format!("{}
+- add: sphere
+  end-transform:
+    - [shear, 1, 0, 0, 0, 0, 0]
+", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+        let text = format!("{}
+- add: cube
+  end-transform:
+    - [scale, -1, 1, 1]
+", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+        let text = format!("{}
+- add: plane
+  end-transform:
+    - [translate, 1, 0, 0]
+", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+    }
+
+    #[test]
+    //Tests loading animated values at different frames
+    fn load_keyframes() {
+        let text = format!("{}
+- add: light
+  at:
+    keyframes:
+      - [0, [-10, 10, -10]]
+      - [10, [10, 10, -10]]
+  intensity: [1, 1, 1]
+- add: sphere
+  material:
+    ambient: {{ keyframes: [[0, 0], [4, 1]], interpolation: ease }}
+  transform:
+    - [translate, {{ keyframes: [{{ frame: 0, value: 0, interpolation: [bezier, 0, 0, 1, 1] }}, [10, 5]] }}, 0, 0]
+", CAMERA.replace("from: [0, 0, -5]", "from: { keyframes: [[0, [0, 0, -5]], [10, [0, 0, -10]]] }"));
+        let (scene, camera) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(camera.transform, Matrix4x4::view_transform(
+            Vec4::new(0.0, 0.0, -5.0, 1.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+            Vec4::new(0.0, 1.0, 0.0, 0.0),
+        ));
+        assert_eq!(scene.light_sources[0].get_position(), &Vec4(-10.0, 10.0, -10.0, 1.0));
+        let sphere = scene.objects[0].as_any().downcast_ref::<Sphere>().unwrap();
+        assert_eq!(sphere.material.ambient, 0.0);
+
+        let (scene, camera) = SceneFile::parse_frame(&text, Path::new(""), 5.0).unwrap();
+        assert_eq!(camera.transform, Matrix4x4::view_transform(
+            Vec4::new(0.0, 0.0, -7.5, 1.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+            Vec4::new(0.0, 1.0, 0.0, 0.0),
+        ));
+        assert_eq!(scene.light_sources[0].get_position(), &Vec4(0.0, 10.0, -10.0, 1.0));
+        let sphere = scene.objects[0].as_any().downcast_ref::<Sphere>().unwrap();
+        assert_eq!(sphere.material.ambient, 1.0);
+        assert!((sphere.transform.get(0, 3) - 2.5).abs() < 0.001);
+
+        let bad = ["[[0, 1], [5, [1, 2, 3]]]", "[[0, 1], [0, 2]]", "[]", "[[0, 1]], interpolation: bounce"];
+        for keyframes in bad.iter() {
+            let text = CAMERA.replace("field-of-view: 1.5708", &format!("field-of-view: {{ keyframes: {} }}", keyframes));
+            assert!(SceneFile::parse(&text, Path::new("")).is_err());
+        }
+    }
+
+    #[test]
+    //Tests loading orthographic and panoramic cameras
+    fn load_projections() {
+        let text = CAMERA.replace("field-of-view: 1.5708", "projection: orthographic\n  view-width: 8");
+        let (_, camera) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(camera.projection, Projection::Orthographic);
+        assert_eq!(camera.half_width, 4.0);
+        assert_eq!(camera.half_height, 2.0);
+
+        let text = CAMERA.replace("field-of-view", "projection: fisheye\n  field-of-view");
+        let (_, camera) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert!(matches!(camera.projection, Projection::Fisheye(fov) if (fov - 90.0).abs() < 0.001));
+
+        let text = CAMERA.replace("field-of-view: 1.5708", "projection: equirectangular");
+        let (_, camera) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(camera.projection, Projection::Equirectangular);
+
+        let text = CAMERA.replace("field-of-view: 1.5708", "projection: isometric");
+        assert_eq!(SceneFile::parse(&text, Path::new("")).err().unwrap().message, "unknown projection 'isometric'");
+    }
+
+    #[test]
+    //Tests loading a camera, lights and shapes
+    fn load_scene() {
+        let text = format!("{}
+- add: light
+  at: [-10, 10, -10]
+  intensity: [1, 1, 1]
+- add: light
+  corner: [0, 5, 0]
+  uvec: [1, 0, 0]
+  usteps: 2
+  vvec: [0, 0, 1]
+  vsteps: 2
+  intensity: [0.5, 0.5, 0.5]
+- add: sphere
+  material:
+    color: [1, 0, 0]
+    diffuse: 0.7
+  transform:
+    - [scale, 2, 2, 2]
+    - [translate, 0, 1, 0]
+- add: plane
+", CAMERA);
+        let (scene, camera) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(camera.hsize, 100);
+        assert_eq!(camera.vsize, 50);
+        assert_eq!(camera.aperture, 0.0);
+        assert_eq!(camera.focal_distance, 5.0);
+        assert_eq!(scene.light_sources.len(), 2);
+        assert_eq!(scene.light_sources[0].get_position(), &Vec4(-10.0, 10.0, -10.0, 1.0));
+        assert_eq!(scene.light_sources[1].get_intensity(), &Color::new(0.5, 0.5, 0.5));
+        assert_eq!(scene.objects.len(), 2);
+
+        let sphere = scene.objects[0].as_any().downcast_ref::<Sphere>().unwrap();
+        assert_eq!(sphere.transform, Matrix4x4::translation(0.0, 1.0, 0.0) * Matrix4x4::scaling(2.0, 2.0, 2.0));
+        assert_eq!(sphere.material.color, Color::new(1.0, 0.0, 0.0));
+        assert_eq!(sphere.material.diffuse, 0.7);
+
+        let intersections = Ray::intersect_scene(&scene, Ray::new((0.0, 1.0, -5.0), (0.0, 0.0, 1.0)));
+        let hit = intersections.iter().find(|i| i.t > 0.0).unwrap();
+        assert_eq!(hit.t, 3.0);
+    }
+
+    #[test]
+    //Tests defines which extend other defines
+    fn extend_define() {
+        let text = format!("{}
+- define: base
+  value:
+    color: [1, 1, 1]
+    ambient: 0.5
+- define: red
+  extend: base
+  value:
+    color: [1, 0, 0]
+- define: move-up
+  value:
+    - [translate, 0, 2, 0]
+- add: sphere
+  material: red
+  transform:
+    - move-up
+    - [scale, 2, 2, 2]
+", CAMERA);
+        let (scene, _) = SceneFile::parse(&text, Path::new("")).unwrap();
+        let sphere = scene.objects[0].as_any().downcast_ref::<Sphere>().unwrap();
+        assert_eq!(sphere.material.color, Color::new(1.0, 0.0, 0.0));
+        assert_eq!(sphere.material.ambient, 0.5);
+        assert_eq!(sphere.transform, Matrix4x4::scaling(2.0, 2.0, 2.0) * Matrix4x4::translation(0.0, 2.0, 0.0));
+    }
+
+    #[test]
+    //Tests groups, csgs and obj files
+    fn load_groups() {
+        let text = format!("{}
+- add: group
+  transform:
+    - [translate, 5, 0, 0]
+  children:
+    - add: cube
+      material:
+        color: [0, 1, 0]
+    - add: csg
+      operation: difference
+      left:
+        add: sphere
+      right:
+        add: cube
+        transform:
+          - [translate, 1, 0, 0]
+- add: obj
+  file: test1.obj
+", CAMERA);
+        let (scene, _) = SceneFile::parse(&text, Path::new("tests")).unwrap();
+        assert_eq!(scene.objects.len(), 2);
+        let group = scene.objects[0].as_any().downcast_ref::<Group>().unwrap();
+        assert_eq!(group.objects.len(), 2);
+        assert_eq!(group.objects[0].get_parent_material(), &None);
+        let obj = scene.objects[1].as_any().downcast_ref::<Group>().unwrap();
+        assert_eq!(obj.objects.len(), 2);
+        assert_eq!(scene.shape_count(), 5);
+
+        let intersections = Ray::intersect_scene(&scene, Ray::new((5.0, 0.0, -5.0), (0.0, 0.0, 1.0)));
+        assert_eq!(intersections[0].t, 4.0);
+
+        //Missing and malformed obj files are errors at the obj entry
+        for (file, message) in [("missing.obj", "failed to read tests/missing.obj"), ("bad.obj", "line 3: index 4 is out of range")].iter() {
+            let text = format!("{}\n- add: obj\n  file: {}\n", CAMERA, file);
+            let error = SceneFile::parse(&text, Path::new("tests")).err().unwrap();
+            assert_eq!(error.line, 10);
+            assert!(error.message.contains(message), "{}", error.message);
+        }
+    }
+
+    #[test]
+    //Tests that large scenes still count every shape once they are divided into a hierarchy
+    fn count_divided_shapes() {
+        let text = format!("{}{}", CAMERA, "- add: sphere\n".repeat(6));
+        let (scene, _) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(scene.objects.len(), 1);
+        assert_eq!(scene.shape_count(), 6);
+    }
+
+    #[test]
+    //Tests loading an image texture relative to the scene directory
+    fn load_texture() {
+        let text = format!("{}
+- add: plane
+  material:
+    pattern:
+      type: texture
+      file: test_palette.png
+      mapping: planar
+      filter: nearest
+", CAMERA);
+        let (scene, _) = SceneFile::parse(&text, Path::new("tests")).unwrap();
+        let material = scene.objects[0].get_material();
+        let pattern = material.pattern.as_ref().unwrap();
+        assert_eq!(pattern.color_at(&Vec4(0.1, 0.0, 0.5, 1.0)), Color::new(1.0, 0.0, 0.0));
+        assert_eq!(pattern.color_at(&Vec4(0.6, 0.0, 0.5, 1.0)), Color::new(0.0, 0.0, 1.0));
+
+        let text = format!("{}
+- add: plane
+  material:
+    pattern:
+      type: texture
+      file: missing.png
+", CAMERA);
+        assert_eq!(SceneFile::parse(&text, Path::new("tests")).err().unwrap().line, 14);
+    }
+
+    #[test]
+    //Tests loading each kind of background
+    fn load_background() {
+        let (scene, _) = SceneFile::parse(&format!("{}\n- add: background\n  color: [0.1, 0.2, 0.3]\n", CAMERA), Path::new("")).unwrap();
+        assert_eq!(scene.background, Background::Color(Color::new(0.1, 0.2, 0.3)));
+        let (scene, _) = SceneFile::parse(&format!("{}\n- add: background\n  top: [1, 1, 1]\n  bottom: [0, 0, 0]\n", CAMERA), Path::new("")).unwrap();
+        assert_eq!(scene.background, Background::Gradient { top: Color::new(1.0, 1.0, 1.0), bottom: Color::new(0.0, 0.0, 0.0) });
+        let (scene, _) = SceneFile::parse(&format!("{}\n- add: background\n  file: test_rgba.png\n", CAMERA), Path::new("tests")).unwrap();
+        assert!(matches!(scene.background, Background::Environment(_)));
+    }
+
+    #[test]
+    //Tests loading spot lights and directional lights
+    fn load_spot_and_directional_lights() {
+        let text = format!("{}
+- add: light
+  at: [0, 10, 0]
+  direction: [0, -2, 0]
+  inner-angle: 0.2
+  outer-angle: 0.4
+  intensity: [1, 1, 1]
+- add: light
+  direction: [0, 0, 3]
+  intensity: [0.5, 0.5, 0.5]
+", CAMERA);
+        let (scene, _) = SceneFile::parse(&text, Path::new("")).unwrap();
+        let spot = &scene.light_sources[0];
+        assert_eq!(spot.get_position(), &Vec4(0.0, 10.0, 0.0, 1.0));
+        assert_eq!(spot.light_intensity(&Vec4(0.0, 0.0, 0.0, 1.0), &scene), Color::new(1.0, 1.0, 1.0));
+        assert_eq!(spot.light_intensity(&Vec4(10.0, 0.0, 0.0, 1.0), &scene), Color::new(0.0, 0.0, 0.0));
+        let sun = &scene.light_sources[1];
+        assert_eq!(sun.get_position(), &Vec4(0.0, 0.0, 1.0, 0.0));
+        assert_eq!(sun.get_intensity(), &Color::new(0.5, 0.5, 0.5));
+
+        let text = format!("{}\n- add: light\n  direction: [0, 0, 0]\n  intensity: [1, 1, 1]\n", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+        let text = format!("{}\n- add: light\n  at: [0, 0, 0]\n  direction: [0, 1, 0]\n  inner-angle: 0.5\n  outer-angle: 0.2\n  intensity: [1, 1, 1]\n", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+    }
+
+    #[test]
+    //Tests loading light attenuation from a name or coefficients
+    fn load_attenuation() {
+        let text = format!("{}
+- add: light
+  at: [0, 2, 0]
+  intensity: [1, 1, 1]
+  attenuation: inverse-square
+- add: light
+  at: [0, 4, 0]
+  intensity: [1, 1, 1]
+  attenuation: [1, 0, 1]
+", CAMERA);
+        let (scene, _) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(scene.light_sources[0].get_attenuation(), &Attenuation::InverseSquare);
+        assert_eq!(scene.light_sources[1].get_attenuation(), &Attenuation::Coefficients { constant: 1.0, linear: 0.0, quadratic: 1.0 });
+
+        let text = format!("{}\n- add: light\n  at: [0, 0, 0]\n  intensity: [1, 1, 1]\n  attenuation: cubic\n", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+        let text = format!("{}\n- add: light\n  direction: [0, -1, 0]\n  intensity: [1, 1, 1]\n  attenuation: linear\n", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+    }
+
+    #[test]
+    //Tests loading sphere and disk lights with a sampling strategy
+    fn load_sphere_and_disk_lights() {
+        let text = format!("{}
+- add: light
+  center: [0, 5, 0]
+  radius: 1
+  usteps: 4
+  vsteps: 2
+  sampling: low-discrepancy
+  intensity: [1, 1, 1]
+- add: light
+  center: [0, 5, 0]
+  normal: [0, -1, 0]
+  radius: 2
+  usteps: 3
+  vsteps: 3
+  intensity: [1, 1, 1]
+", CAMERA);
+        let (scene, _) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(scene.light_sources[0].get_positions().len(), 8);
+        assert_eq!(scene.light_sources[1].get_positions().len(), 9);
+        assert_eq!(scene.light_sources[1].light_intensity(&Vec4(0.0, 10.0, 0.0, 1.0), &scene), Color::new(0.0, 0.0, 0.0));
+
+        let text = format!("{}\n- add: light\n  center: [0, 0, 0]\n  radius: 1\n  usteps: 2\n  vsteps: 2\n  sampling: random\n  intensity: [1, 1, 1]\n", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+        let text = format!("{}\n- add: light\n  center: [0, 0, 0]\n  radius: 0\n  usteps: 2\n  vsteps: 2\n  intensity: [1, 1, 1]\n", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+    }
+
+    #[test]
+    //Tests that visible lights add a surface to the scene
+    fn load_visible_light() {
+        let text = format!("{}
+- add: light
+  center: [0, 5, 0]
+  radius: 1
+  usteps: 2
+  vsteps: 2
+  visible: true
+  intensity: [1, 1, 1]
+- add: light
+  corner: [0, 5, 0]
+  uvec: [1, 0, 0]
+  vvec: [0, 0, 1]
+  usteps: 2
+  vsteps: 2
+  intensity: [1, 1, 1]
+", CAMERA);
+        let (scene, _) = SceneFile::parse(&text, Path::new("")).unwrap();
+        assert_eq!(scene.light_sources.len(), 2);
+        assert_eq!(scene.objects.len(), 1);
+        assert_eq!(scene.objects[0].get_material().emission, Color::new(1.0, 1.0, 1.0));
+
+        let text = format!("{}\n- add: light\n  at: [0, 0, 0]\n  visible: true\n  intensity: [1, 1, 1]\n", CAMERA);
+        assert!(SceneFile::parse(&text, Path::new("")).is_err());
+    }
+
+    #[test]
+    //Tests loading the example scene from disk
+    fn load_example() {
+        let (scene, camera) = SceneFile::load("scenes/example.yml").unwrap();
+        assert_eq!(camera.hsize, 400);
+        assert_eq!(scene.light_sources.len(), 1);
+        assert_eq!(scene.objects.len(), 3);
+    }
+
+    #[test]
+    //Tests that errors point to the line which caused them
+    fn line_numbered_errors() {
+        let text = format!("{}
+- add: sphere
+  material:
+    colour: [1, 0, 0]
+", CAMERA);
+        let error = SceneFile::parse(&text, Path::new("")).err().unwrap();
+        assert_eq!(error.line, 12);
+        assert_eq!(error.to_string(), "line 12: unknown material property 'colour'");
+
+        let text = format!("{}
+- add: teapot
+", CAMERA);
+        assert_eq!(SceneFile::parse(&text, Path::new("")).err().unwrap().line, 10);
+
+        let text = format!("{}
+- add: light
+  at: [1, two, 3]
+  intensity: [1, 1, 1]
+", CAMERA);
+        assert_eq!(SceneFile::parse(&text, Path::new("")).err().unwrap().line, 11);
+
+        let text = CAMERA.replace("up: [0, 1, 0]", "up: [0, 1, 0]\n  aperture: 0.1\n  aperture-blades: 2");
+        assert_eq!(SceneFile::parse(&text, Path::new("")).err().unwrap().message, "an aperture needs at least 3 blades");
+
+        let text = format!("{}
+- add: group
+  shadow: false
+  children:
+    - add: sphere
+", CAMERA);
+        let error = SceneFile::parse(&text, Path::new("")).err().unwrap();
+        assert_eq!(error.line, 11);
+        assert_eq!(error.message, "a group needs a material to set whether its children cast shadows");
+        let text = text.replace("  shadow: false", "  shadow: false\n  material: { color: [1, 0, 0] }");
+        let (scene, _) = SceneFile::parse(&text, Path::new("")).unwrap();
+        let group = scene.objects[0].as_any().downcast_ref::<Group>().unwrap();
+        assert!(!group.objects[0].get_effective_material().casts_shadows);
+
+        let error = SceneFile::parse("- add: sphere", Path::new("")).err().unwrap();
+        assert_eq!(error.message, "the scene does not add a camera");
+    }
+}
@@ -7,6 +7,9 @@ mod tests {
     use rust_ray_tracer::core::vector::Vec4;
     use rust_ray_tracer::objects::object::*;
     use rust_ray_tracer::materials::material::Material;
+    use rust_ray_tracer::objects::motion::Motion;
+    use rust_ray_tracer::objects::bounds::BoundingBox;
+    use rust_ray_tracer::ray_tracing::ray::Ray;
 
     #[test]
     //Tests surface normals on the x axis
@@ -47,4 +50,21 @@ mod tests {
         let vector = s.normal(&Vec4::new(1.0, 0.0, 0.0, 0.0), None, None);
         assert_eq!(vector.round(), Vec4::new(1.0, 0.0, 0.0, 0.0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    //Tests intersecting a moving sphere at different times
+    fn moving_sphere() {
+        let mut s = Sphere::new(Matrix4x4::identity(), Material::default());
+        s.motion = Some(Motion::new(Matrix4x4::translation(4.0, 0.0, 0.0)));
+        let ray = Ray::new((4.0, 0.0, -5.0), (0.0, 0.0, 1.0));
+        assert!(s.intersect(&ray).is_none());
+        let hits = s.intersect(&ray.clone().at_time(1.0)).unwrap();
+        assert_eq!((hits[0].t, hits[1].t), (4.0, 6.0));
+        assert_eq!(hits[0].normal, Vec4::new(0.0, 0.0, -1.0, 0.0));
+
+        let ray = Ray::new((2.0, 0.0, -5.0), (0.0, 0.0, 1.0)).at_time(0.5);
+        assert_eq!(s.intersect(&ray).unwrap()[0].t, 4.0);
+        assert_eq!(s.normal_at(&Vec4::new(3.0, 0.0, 0.0, 1.0), None, None, 0.5), Vec4::new(1.0, 0.0, 0.0, 0.0));
+        assert_eq!(s.bounds(), BoundingBox::new(Vec4(-1.0, -1.0, -1.0, 1.0), Vec4(5.0, 1.0, 1.0, 1.0)));
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use rust_ray_tracer::core::canvas::Canvas;
+    use rust_ray_tracer::core::color::*;
+    use rust_ray_tracer::core::matrix::Matrix4x4;
+    use rust_ray_tracer::core::vector::Vec4;
+    use rust_ray_tracer::ray_tracing::ray::Ray;
+    use rust_ray_tracer::world::camera::Camera;
+    use rust_ray_tracer::world::stereo::*;
+
+    fn rig(convergence: Convergence) -> StereoRig {
+        let mut camera = Camera::new(11, 11, 90.0);
+        camera.transform(Matrix4x4::view_transform(
+            Vec4::new(0.0, 0.0, -5.0, 1.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+            Vec4::new(0.0, 1.0, 0.0, 0.0),
+        ));
+        let mut rig = StereoRig::new(camera, 0.5, 5.0);
+        rig.convergence = convergence;
+        rig
+    }
+
+    //Tests that each eye sits beside the camera and that their views meet at the convergence distance
+    #[test]
+    fn eye_cameras() {
+        for convergence in [Convergence::OffAxis, Convergence::ToeIn].iter() {
+            let rig = rig(*convergence);
+            let left = Camera::ray_towards_pixel(&rig.eye(Eye::Left), 5, 5);
+            let right = Camera::ray_towards_pixel(&rig.eye(Eye::Right), 5, 5);
+            assert_eq!(left.origin.round(), Vec4::new(-0.25, 0.0, -5.0, 1.0).round());
+            assert_eq!(right.origin.round(), Vec4::new(0.25, 0.0, -5.0, 1.0).round());
+            let t = 5.0 / left.direction.2;
+            assert_eq!(Ray::position(&left, t).round(), Vec4::new(0.0, 0.0, 0.0, 1.0).round());
+            let t = 5.0 / right.direction.2;
+            assert_eq!(Ray::position(&right, t).round(), Vec4::new(0.0, 0.0, 0.0, 1.0).round());
+        }
+
+        //Off-axis eyes keep looking straight ahead while toed-in eyes turn towards each other
+        let forward = Vec4::new(0.0, 0.0, -1.0, 0.0);
+        let off_axis = rig(Convergence::OffAxis).eye(Eye::Left);
+        assert_eq!((off_axis.transform.inverse().unwrap() * forward.clone()).round(), Vec4::new(0.0, 0.0, 1.0, 0.0));
+        let toe_in = rig(Convergence::ToeIn).eye(Eye::Left);
+        assert!((toe_in.transform.inverse().unwrap() * forward).0 > 0.0);
+    }
+
+    //Tests placing the view from each eye on its half of the canvas
+    #[test]
+    fn composite_layouts() {
+        let mut rig = rig(Convergence::OffAxis);
+        assert_eq!(rig.size(), (22, 11));
+        let mut canvas = Canvas::new(22, 11);
+        let mut eyes = vec![];
+        StereoRig::render(&rig, &mut canvas, |camera, view| {
+            let color = if eyes.is_empty() { WHITE } else { Color::new(0.0, 0.0, 1.0) };
+            eyes.push(camera.transform.clone());
+            view.contents = vec![color; view.contents.len()];
+        });
+        assert_eq!(eyes, vec![rig.eye(Eye::Left).transform, rig.eye(Eye::Right).transform]);
+        assert_eq!(canvas.get(10, 10), Some(&WHITE));
+        assert_eq!(canvas.get(11, 0), Some(&Color::new(0.0, 0.0, 1.0)));
+
+        rig.layout = Layout::TopBottom;
+        assert_eq!(rig.size(), (11, 22));
+        let mut canvas = Canvas::new(11, 22);
+        let view = Canvas::new(11, 11);
+        let mut white = Canvas::new(11, 11);
+        white.contents = vec![WHITE; 121];
+        rig.place(&mut canvas, &white, Eye::Left);
+        rig.place(&mut canvas, &view, Eye::Right);
+        assert_eq!(canvas.get(10, 10), Some(&WHITE));
+        assert_eq!(canvas.get(0, 11), Some(&BLACK));
+    }
+}